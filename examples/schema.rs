@@ -7,7 +7,7 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_template::{
-  msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ResponseMsg},
+  msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ResponseMsg},
   utils::Config,
 };
 
@@ -20,6 +20,7 @@ fn main() {
   // List of message types
   export_schema(&schema_for!(InstantiateMsg), &out_dir);
   export_schema(&schema_for!(ExecuteMsg), &out_dir);
+  export_schema(&schema_for!(MigrateMsg), &out_dir);
   export_schema(&schema_for!(QueryMsg), &out_dir);
   export_schema(&schema_for!(ResponseMsg), &out_dir);
 
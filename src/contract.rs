@@ -1,45 +1,730 @@
 // The file is responsible for storing list of contract methods
 
-use cosmwasm_std::{entry_point, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{
+  entry_point, from_binary, to_binary, Addr, BankMsg, Binary, CanonicalAddr, Coin, CosmosMsg, Deps, DepsMut, Env,
+  MessageInfo, Response, StdResult, Storage, Uint128, WasmMsg,
+};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ResponseMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg, ResponseMsg, PAUSABLE_ACTIONS};
 use crate::state::{
-  create_city, create_person, get_city, get_city_id_by_person, get_person, get_person_address_by_city, get_storage,
-  register_in_city, set_storage, unregister_from_city, update_person,
+  admit_from_waitlist, appoint_deputy, ban_from_city, clear_action_pause_reasons, create_city, create_council_proposal,
+  create_join_request, create_person, create_region, delete_city_batch, dismiss_deputy, escrow_cw20_join_fee,
+  escrow_join_fee, evict_overage_batch, get_action_pause_reasons, get_administered_cities, get_candidates,
+  get_child_regions, get_cities, get_cities_in_area, get_city, get_city_balances, get_city_bans, get_city_changes,
+  get_city_cw20_balances, get_city_deputies, get_city_id_by_person, get_city_join_requests, get_council_proposal,
+  get_council_proposals, get_cw20_join_fees, get_cw20_refunds, get_election, get_join_request, get_legacy_config,
+  get_maintainer_claim, get_maintainer_proposal, get_nearest_cities, get_person, get_person_address_by_city,
+  get_person_join_requests, get_protocol_balances, get_protocol_cw20_balances, get_refunds, get_region,
+  get_region_cities, get_region_path, get_role_holders, get_roles_by_address, get_storage, grant_role, has_city_invite,
+  has_cw20_join_fees, has_role, index_city_names_batch, join_city, leave_waitlist, may_get_action_pause_reason,
+  may_get_candidate_votes, may_get_city_ban, may_get_city_id_by_name, may_get_city_office, may_get_council,
+  may_get_cw20_join_fee, may_get_election, may_get_escrowed_cw20_join_fee, may_get_escrowed_join_fee,
+  may_get_invite_code, may_get_maintainer_claim, may_get_maintainer_proposal, may_get_pause_reason, may_get_vote,
+  may_get_waitlist_position, merge_city_batch, remove_action_pause_reason, remove_city_ban, remove_city_invite,
+  remove_council, remove_council_proposal, remove_invite_code, remove_join_request, remove_maintainer_claim,
+  remove_maintainer_proposal, remove_pause_reason, remove_region, resolve_city_id, revoke_role,
+  set_action_pause_reason, set_candidate_votes, set_city_age_limits, set_city_capacity, set_city_invite,
+  set_city_profile, set_city_region, set_city_status, set_council, set_council_proposal, set_cw20_join_fee,
+  set_election, set_invite_code, set_join_fee, set_join_policy, set_maintainer_claim, set_maintainer_proposal,
+  set_mayor, set_pause_reason, set_storage, set_vote, settle_join_fee, start_city_merge, start_city_names_indexing,
+  unregister_from_city, update_city, update_person, update_region, use_invite_code, withdraw_city_balance,
+  withdraw_city_cw20_balance, withdraw_cw20_refund, withdraw_protocol_balance, withdraw_protocol_cw20_balance,
+  withdraw_refund,
+};
+use crate::utils::{
+  AdministeredCityResponse, Birthday, CandidateResponse, City, CityBan, CityBanResponse, CityChangeResponse, CityName,
+  CityOffice, CityProfile, CityResponse, CityStatus, Config, ConfigResponse, Coordinates, Council, CouncilProposal,
+  CouncilProposalResponse, CouncilResponse, Election, ElectionResponse, ElectionStatus, Email, InviteCode,
+  InviteCodeResponse, JoinPolicy, JoinRequest, JoinRequestResponse, MaintainerClaim, MaintainerClaimResponse,
+  MaintainerProposal, MaintainerProposalResponse, Nickname, PausedAction, PersonResponse, Region, RegionName,
+  RegionResponse, Role, Vote, BASIS_POINTS, DAY_IN_SECONDS, MAX_LATITUDE, MAX_LONGITUDE, YEAR_IN_SECONDS,
 };
-use crate::utils::{Birthday, CityName, CityResponse, Config, Email, Nickname, PersonResponse};
+use cw2::{set_contract_version, CONTRACT};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw_utils::{must_pay, nonpayable, Expiration};
+use semver::Version;
+use sha2::{Digest, Sha256};
+
+// Contract name and version stored by cw2 for migration checks
+const CONTRACT_NAME: &str = "crates.io:cosmwasm_template";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+// Contract instantiated before cw2 versioning has no stored version
+const LEGACY_CONTRACT_VERSION: &str = "1.0.0";
+
+const MAX_CITY_DEPUTIES: u64 = 10;
+const MAX_ELECTION_CANDIDATES: u64 = 20;
+const MAX_WAITLIST_ADMISSIONS: u64 = 50;
+const MAX_EVICTION_SCAN: u64 = 50;
+const MAX_CITY_NAMES_INDEXED: u64 = 100;
+const SHA256_HASH_LENGTH: usize = 32;
+const MAX_CITY_NAME_LENGTH: usize = 64;
+const MAX_CITY_DESCRIPTION_LENGTH: usize = 1024;
+const MAX_URI_LENGTH: usize = 256;
+const MAX_KICK_REASON_LENGTH: usize = 256;
+
+// Default config values used when instantiate message field is missed
+const DEFAULT_MAINTAINER_REQUIREMENT_NAME: &str = "Super_Maintainer_887";
+const DEFAULT_MAINTAINER_REQUIREMENT_AGE: u8 = 17;
+const DEFAULT_MAINTAINER_CLAIM_WINDOW: u64 = 7 * DAY_IN_SECONDS;
+const DEFAULT_ELECTION_VOTING_PERIOD: u64 = 7 * DAY_IN_SECONDS;
+const DEFAULT_ELECTION_QUORUM: u64 = 1;
+const DEFAULT_MAX_PAGE_SIZE: u64 = 100;
+const DEFAULT_CITY_DELETION_BATCH_SIZE: u64 = 50;
+const DEFAULT_JOIN_REQUEST_PERIOD: u64 = 7 * DAY_IN_SECONDS;
+const DEFAULT_PROTOCOL_FEE_SHARE: u64 = 0;
+
+// Helper function for checking caller has maintainer access level
+// In council mode the level is granted only to the contract itself while executing approved council proposal
+fn check_maintainer(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+  // Get config from storage
+  let config = get_storage(deps.storage)?;
+
+  // Nobody has maintainer access level in frozen mode
+  if config.frozen {
+    return Err(ContractError::Frozen {});
+  }
+
+  if may_get_council(deps.storage)?.is_some() {
+    if info.sender != env.contract.address {
+      return Err(ContractError::CouncilApprovalRequired {});
+    }
+
+    return Ok(());
+  }
+
+  // Check caller is maintainer
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if config.maintainer != Some(canonical_sender) {
+    return Err(ContractError::Unauthorized {});
+  }
+
+  Ok(())
+}
+
+// Helper function for checking caller has the role or maintainer access level
+fn check_role(deps: Deps, env: &Env, info: &MessageInfo, role: Role) -> Result<(), ContractError> {
+  match check_maintainer(deps, env, info) {
+    Ok(()) => return Ok(()),
+    Err(ContractError::Frozen {}) => return Err(ContractError::Frozen {}),
+    Err(_) => {}
+  }
+
+  // Check caller holds the role
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if !has_role(deps.storage, &canonical_sender, role) {
+    return Err(ContractError::MissingRole {
+      role: role.key().to_string(),
+    });
+  }
+
+  Ok(())
+}
+
+// Helper function for checking caller is guardian or has maintainer access level
+fn check_guardian(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+  match check_maintainer(deps, env, info) {
+    Ok(()) => return Ok(()),
+    Err(ContractError::Frozen {}) => return Err(ContractError::Frozen {}),
+    Err(_) => {}
+  }
+
+  // Get config from storage
+  let config = get_storage(deps.storage)?;
+
+  // Check caller is guardian
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if config.guardian != Some(canonical_sender) {
+    return Err(ContractError::NotGuardian {});
+  }
+
+  Ok(())
+}
+
+// Helper function for checking neither the whole execute entrypoint nor the action is paused
+fn check_not_paused(deps: Deps, msg: &ExecuteMsg) -> Result<(), ContractError> {
+  if !msg.is_pausable() {
+    return Ok(());
+  }
+
+  // Check the whole execute entrypoint first
+  let reason = match may_get_pause_reason(deps.storage)? {
+    Some(reason) => Some(reason),
+    None => may_get_action_pause_reason(deps.storage, msg.action())?,
+  };
+
+  if let Some(reason) = reason {
+    return Err(ContractError::Paused {
+      action: msg.action().to_string(),
+      reason,
+    });
+  }
+
+  Ok(())
+}
+
+// Helper function for checking caller is city admin or has maintainer access level
+fn check_city_admin(deps: Deps, env: &Env, info: &MessageInfo, city_id: u64) -> Result<(), ContractError> {
+  match check_maintainer(deps, env, info) {
+    Ok(()) => return Ok(()),
+    Err(ContractError::Frozen {}) => return Err(ContractError::Frozen {}),
+    Err(_) => {}
+  }
+
+  // Check caller is city mayor or deputy
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if may_get_city_office(deps.storage, &canonical_sender, city_id)?.is_none() {
+    return Err(ContractError::NotCityAdmin {});
+  }
+
+  Ok(())
+}
+
+// Helper function for checking caller is city admin, moderator or has maintainer access level
+fn check_city_moderator(deps: Deps, env: &Env, info: &MessageInfo, city_id: u64) -> Result<(), ContractError> {
+  match check_city_admin(deps, env, info, city_id) {
+    Ok(()) => return Ok(()),
+    Err(ContractError::Frozen {}) => return Err(ContractError::Frozen {}),
+    Err(_) => {}
+  }
+
+  check_role(deps, env, info, Role::Moderator)
+}
+
+// Helper function for checking caller is city mayor or has maintainer access level
+fn check_mayor(deps: Deps, env: &Env, info: &MessageInfo, city_id: u64) -> Result<(), ContractError> {
+  match check_maintainer(deps, env, info) {
+    Ok(()) => return Ok(()),
+    Err(ContractError::Frozen {}) => return Err(ContractError::Frozen {}),
+    Err(_) => {}
+  }
+
+  // Check caller is city mayor
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if may_get_city_office(deps.storage, &canonical_sender, city_id)? != Some(CityOffice::Mayor) {
+    return Err(ContractError::NotMayor {});
+  }
+
+  Ok(())
+}
+
+// Helper function for validating city name is not blank and not too long
+fn validate_city_name(name: &str) -> Result<(), ContractError> {
+  if name.trim().is_empty() || name.len() > MAX_CITY_NAME_LENGTH {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  Ok(())
+}
+
+// Helper function for validating URI, scheme should consist of letters, digits, '+', '-' and '.'
+fn is_valid_uri(uri: &str) -> bool {
+  let (scheme, rest) = match uri.split_once("://") {
+    Some(parts) => parts,
+    None => return false,
+  };
+
+  scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+    && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    && !rest.is_empty()
+    && !uri.chars().any(|c| c.is_whitespace() || c.is_control())
+    && uri.len() <= MAX_URI_LENGTH
+}
+
+// Helper function for validating coordinates are within range
+fn validate_coordinates(coordinates: &Coordinates) -> Result<(), ContractError> {
+  if coordinates.latitude.abs() > MAX_LATITUDE || coordinates.longitude.abs() > MAX_LONGITUDE {
+    return Err(ContractError::InvalidCoordinates {});
+  }
+
+  Ok(())
+}
+
+// Helper function for validating optional city metadata
+fn validate_city_profile(env: &Env, profile: &CityProfile) -> Result<(), ContractError> {
+  // Validate description
+  if let Some(description) = &profile.description {
+    if description.trim().is_empty() || description.len() > MAX_CITY_DESCRIPTION_LENGTH {
+      return Err(ContractError::InvalidCityDescription {
+        max_length: MAX_CITY_DESCRIPTION_LENGTH,
+      });
+    }
+  }
+
+  // Validate country code format
+  if let Some(country_code) = &profile.country_code {
+    if country_code.len() != 2 || !country_code.chars().all(|c| c.is_ascii_uppercase()) {
+      return Err(ContractError::InvalidCountryCode {});
+    }
+  }
+
+  // Validate coordinates range
+  if let Some(coordinates) = &profile.coordinates {
+    validate_coordinates(coordinates)?;
+  }
+
+  // Validate website and image URIs
+  if let Some(website) = &profile.website {
+    let scheme = website.split_once("://").map(|(scheme, _)| scheme);
+    if !is_valid_uri(website) || !matches!(scheme, Some("http") | Some("https")) {
+      return Err(ContractError::InvalidWebsite {
+        max_length: MAX_URI_LENGTH,
+      });
+    }
+  }
+  if let Some(image_uri) = &profile.image_uri {
+    if !is_valid_uri(image_uri) {
+      return Err(ContractError::InvalidImageUri {
+        max_length: MAX_URI_LENGTH,
+      });
+    }
+  }
+
+  // Validate founding date is existing date not after current year
+  if let Some(founded_on) = &profile.founded_on {
+    let current_year = (env.block.time.seconds() / YEAR_IN_SECONDS) as i32 + 1970;
+    let days_in_month = match founded_on.month {
+      None => None,
+      Some(1 | 3 | 5 | 7 | 8 | 10 | 12) => Some(31),
+      Some(4 | 6 | 9 | 11) => Some(30),
+      Some(2) if founded_on.year % 4 == 0 && (founded_on.year % 100 != 0 || founded_on.year % 400 == 0) => Some(29),
+      Some(2) => Some(28),
+      Some(_) => return Err(ContractError::InvalidFoundingDate {}),
+    };
+    let day_is_valid = match (founded_on.day, days_in_month) {
+      (None, _) => true,
+      (Some(day), Some(days_in_month)) => day >= 1 && day <= days_in_month,
+      (Some(_), None) => false,
+    };
+    if founded_on.year > current_year || !day_is_valid {
+      return Err(ContractError::InvalidFoundingDate {});
+    }
+  }
+
+  Ok(())
+}
+
+// Helper function for representing result of joining city
+fn join_status(registered: bool) -> &'static str {
+  if registered {
+    "registered"
+  } else {
+    "waitlisted"
+  }
+}
+
+// Helper function for checking funds sent to join city cover its join fee
+// Returns paid join fee and refund of overpayment
+fn pay_join_fee(info: &MessageInfo, join_fee: &Option<Coin>) -> Result<(Option<Coin>, Option<BankMsg>), ContractError> {
+  let join_fee = match join_fee {
+    Some(join_fee) => join_fee,
+    None => {
+      nonpayable(info)?;
+      return Ok((None, None));
+    }
+  };
+
+  let paid = must_pay(info, &join_fee.denom)?;
+  if paid < join_fee.amount {
+    return Err(ContractError::InsufficientFee {
+      required: join_fee.amount,
+    });
+  }
+
+  let refund = match paid - join_fee.amount {
+    overpaid if overpaid.is_zero() => None,
+    overpaid => Some(BankMsg::Send {
+      to_address: info.sender.to_string(),
+      amount: vec![Coin {
+        denom: join_fee.denom.clone(),
+        amount: overpaid,
+      }],
+    }),
+  };
+
+  Ok((Some(join_fee.clone()), refund))
+}
+
+// Helper function for transferring native denom or cw20 token from contract
+fn transfer_msg(denom: &Denom, amount: Uint128, recipient: &Addr) -> Result<CosmosMsg, ContractError> {
+  Ok(match denom {
+    Denom::Native(denom) => CosmosMsg::Bank(BankMsg::Send {
+      to_address: recipient.to_string(),
+      amount: vec![Coin {
+        denom: denom.clone(),
+        amount,
+      }],
+    }),
+    Denom::Cw20(token) => CosmosMsg::Wasm(WasmMsg::Execute {
+      contract_addr: token.to_string(),
+      msg: to_binary(&Cw20ExecuteMsg::Transfer {
+        recipient: recipient.to_string(),
+        amount,
+      })?,
+      funds: vec![],
+    }),
+  })
+}
+
+// Helper function for representing stored cw20 balances when querying
+fn cw20_coins(deps: Deps, balances: Vec<(CanonicalAddr, Uint128)>) -> Result<Vec<Cw20Coin>, ContractError> {
+  balances
+    .into_iter()
+    .map(|(token, amount)| {
+      Ok(Cw20Coin {
+        address: deps.api.addr_humanize(&token)?.to_string(),
+        amount,
+      })
+    })
+    .collect()
+}
+
+// Helper function for validating birthday day and year
+fn validate_birthday(env: &Env, birthday: &Birthday) -> Result<(), ContractError> {
+  // Validate birthday day
+  if let Some(day) = birthday.day {
+    if day > 366 || day == 0 {
+      return Err(ContractError::InconsistentData {});
+    }
+  }
+
+  // Validate birthday year
+  if birthday.year < 1756 || birthday.year > (env.block.time.seconds() / YEAR_IN_SECONDS) as u16 + 1970 {
+    return Err(ContractError::InconsistentData {});
+  }
 
-const YEAR_IN_SECONDS: u64 = 31556952;
-const DAY_IN_SECONDS: u64 = 86400;
+  Ok(())
+}
+
+// Helper function for decoding hex encoded sha256 hash of invite code
+fn decode_code_hash(code_hash: &str) -> Result<Vec<u8>, ContractError> {
+  match hex::decode(code_hash) {
+    Ok(hash) if hash.len() == SHA256_HASH_LENGTH => Ok(hash),
+    _ => Err(ContractError::InconsistentData {}),
+  }
+}
+
+// Helper function for loading council and checking caller is its member
+fn check_council_member(deps: Deps, info: &MessageInfo) -> Result<(Council, CanonicalAddr), ContractError> {
+  let council = may_get_council(deps.storage)?.ok_or(ContractError::NotCouncilMember {})?;
+
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if !council.members.contains(&canonical_sender) {
+    return Err(ContractError::NotCouncilMember {});
+  }
+
+  Ok((council, canonical_sender))
+}
+
+// Helper function for representing City when querying
+fn city_response(id: u64, city: City) -> CityResponse {
+  CityResponse {
+    id,
+    name: city.city_name,
+    power_level: city.power_level,
+    population: city.members_count,
+    status: city.status,
+    join_policy: city.join_policy,
+    max_members: city.max_members,
+    waitlist_length: city.waitlist_count,
+    min_age: city.min_age,
+    max_age: city.max_age,
+    join_fee: city.join_fee,
+    region_id: city.region_id,
+    profile: city.profile,
+    merged_into: city.merged_into,
+  }
+}
+
+// Helper function for representing Region when querying
+fn region_response(id: u64, region: Region) -> RegionResponse {
+  RegionResponse {
+    id,
+    name: region.name,
+    kind: region.kind,
+    parent_id: region.parent_id,
+    regions_count: region.children_count,
+    cities_count: region.cities_count,
+    population: region.population,
+    min_age: region.min_age,
+    max_age: region.max_age,
+  }
+}
 
-const MAINTAINER_REQUIREMENT_NAME: &str = "Super_Maintainer_887";
-const MAINTAINER_REQUIREMENT_AGE: u8 = 17;
+// Helper function for limiting number of items returned by list queries
+fn page_limit(deps: Deps, limit: u64) -> Result<u64, ContractError> {
+  Ok(limit.min(get_storage(deps.storage)?.max_page_size))
+}
 
 // Instantiate contract entrypoint
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(deps: DepsMut, _: Env, info: MessageInfo, _: InstantiateMsg) -> Result<Response, ContractError> {
+pub fn instantiate(deps: DepsMut, _: Env, info: MessageInfo, msg: InstantiateMsg) -> Result<Response, ContractError> {
   // Initialize config structure
+  // Caller is maintainer if another one is not provided
   let config = Config {
-    maintainer: deps.api.addr_canonicalize(info.sender.as_str())?,
+    maintainer: Some(
+      deps
+        .api
+        .addr_canonicalize(msg.maintainer.unwrap_or(info.sender).as_str())?,
+    ),
+    frozen: false,
     cities_count: 0,
+    council_proposals_count: 0,
+    elections_count: 0,
+    regions_count: 0,
+    maintainer_requirement_name: msg
+      .maintainer_requirement_name
+      .unwrap_or_else(|| DEFAULT_MAINTAINER_REQUIREMENT_NAME.to_string()),
+    maintainer_requirement_age: msg
+      .maintainer_requirement_age
+      .unwrap_or(DEFAULT_MAINTAINER_REQUIREMENT_AGE),
+    maintainer_claim_window: msg.maintainer_claim_window.unwrap_or(DEFAULT_MAINTAINER_CLAIM_WINDOW),
+    election_voting_period: msg.election_voting_period.unwrap_or(DEFAULT_ELECTION_VOTING_PERIOD),
+    election_quorum: msg.election_quorum.unwrap_or(DEFAULT_ELECTION_QUORUM),
+    max_page_size: msg.max_page_size.unwrap_or(DEFAULT_MAX_PAGE_SIZE),
+    city_deletion_batch_size: msg.city_deletion_batch_size.unwrap_or(DEFAULT_CITY_DELETION_BATCH_SIZE),
+    join_request_period: msg.join_request_period.unwrap_or(DEFAULT_JOIN_REQUEST_PERIOD),
+    protocol_fee_share: msg.protocol_fee_share.unwrap_or(DEFAULT_PROTOCOL_FEE_SHARE),
+    guardian: None,
   };
 
+  // Validate challenge window, voting period, batch sizes and fee share
+  if config.maintainer_claim_window == 0
+    || config.election_voting_period == 0
+    || config.max_page_size == 0
+    || config.city_deletion_batch_size == 0
+    || config.join_request_period == 0
+    || config.protocol_fee_share > BASIS_POINTS
+  {
+    return Err(ContractError::InconsistentData {});
+  }
+
   // Store config to cold storage
   set_storage(deps.storage, &config)?;
 
+  // Store contract name and version
+  set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+  // Register seed cities
+  for city in msg.seed_cities.unwrap_or_default() {
+    validate_city_name(&city.name)?;
+    create_city(deps.storage, city.name, city.power_level)?;
+  }
+
   // Return default Ok response
   Ok(Response::default())
 }
 
+// Migrate contract entrypoint
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _: Env, _: MigrateMsg) -> Result<Response, ContractError> {
+  // Get stored contract name and version
+  let (contract, version) = match CONTRACT.may_load(deps.storage)? {
+    Some(stored) => (stored.contract, stored.version),
+    None => (CONTRACT_NAME.to_string(), LEGACY_CONTRACT_VERSION.to_string()),
+  };
+
+  // Check the contract is migrated from the same one
+  if contract != CONTRACT_NAME {
+    return Err(ContractError::InvalidMigration { contract });
+  }
+
+  // Check the contract is not downgraded
+  let stored_version: Version = version.parse()?;
+  if stored_version > CONTRACT_VERSION.parse()? {
+    return Err(ContractError::CannotDowngrade {
+      stored: version,
+      current: CONTRACT_VERSION.to_string(),
+    });
+  }
+
+  // Run state migrations of versions newer than stored one
+  if stored_version < Version::new(1, 1, 0) {
+    migrate_to_v1_1(deps.storage)?;
+  }
+
+  // Store new contract version
+  set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+  // Return Ok response with migrated versions
+  Ok(
+    Response::new()
+      .add_attribute("previous_version", version)
+      .add_attribute("version", CONTRACT_VERSION),
+  )
+}
+
+// Version 1.1.0 extends config with maintainer management settings and limits
+fn migrate_to_v1_1(store: &mut dyn Storage) -> Result<(), ContractError> {
+  // Get legacy config from storage
+  let legacy_config = get_legacy_config(store)?;
+
+  // Store config with default values of new fields
+  set_storage(
+    store,
+    &Config {
+      maintainer: Some(legacy_config.maintainer),
+      frozen: false,
+      cities_count: legacy_config.cities_count,
+      council_proposals_count: 0,
+      elections_count: 0,
+      regions_count: 0,
+      maintainer_requirement_name: DEFAULT_MAINTAINER_REQUIREMENT_NAME.to_string(),
+      maintainer_requirement_age: DEFAULT_MAINTAINER_REQUIREMENT_AGE,
+      maintainer_claim_window: DEFAULT_MAINTAINER_CLAIM_WINDOW,
+      election_voting_period: DEFAULT_ELECTION_VOTING_PERIOD,
+      election_quorum: DEFAULT_ELECTION_QUORUM,
+      max_page_size: DEFAULT_MAX_PAGE_SIZE,
+      city_deletion_batch_size: DEFAULT_CITY_DELETION_BATCH_SIZE,
+      join_request_period: DEFAULT_JOIN_REQUEST_PERIOD,
+      protocol_fee_share: DEFAULT_PROTOCOL_FEE_SHARE,
+      guardian: None,
+    },
+  )?;
+
+  // Index names of existing cities, the rest is indexed by IndexCityNames calls
+  start_city_names_indexing(store)?;
+  index_city_names_batch(store, MAX_CITY_NAMES_INDEXED)?;
+
+  Ok(())
+}
+
 // Execute contract entrypoint
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> Result<Response, ContractError> {
+  // Check the action is not paused
+  check_not_paused(deps.as_ref(), &msg)?;
+
   // Route call to corresponding method
   match msg {
     ExecuteMsg::BecomeMaintainer {} => execute_become_maintainer(deps, env, info),
+    ExecuteMsg::VetoMaintainerClaim {} => execute_veto_maintainer_claim(deps, env, info),
+    ExecuteMsg::FinalizeMaintainerClaim {} => execute_finalize_maintainer_claim(deps, env, info),
+    ExecuteMsg::ProposeMaintainer { address, expires_at } => {
+      execute_propose_maintainer(deps, env, info, address, expires_at)
+    }
+    ExecuteMsg::AcceptMaintainer {} => execute_accept_maintainer(deps, env, info),
+    ExecuteMsg::CancelMaintainerProposal {} => execute_cancel_maintainer_proposal(deps, env, info),
+    ExecuteMsg::RenounceMaintainer {} => execute_renounce_maintainer(deps, env, info),
+    ExecuteMsg::UpdateConfig {
+      maintainer_requirement_name,
+      maintainer_requirement_age,
+      election_voting_period,
+      election_quorum,
+      max_page_size,
+      city_deletion_batch_size,
+      join_request_period,
+      protocol_fee_share,
+    } => execute_update_config(
+      deps,
+      env,
+      info,
+      maintainer_requirement_name,
+      maintainer_requirement_age,
+      election_voting_period,
+      election_quorum,
+      max_page_size,
+      city_deletion_batch_size,
+      join_request_period,
+      protocol_fee_share,
+    ),
+    ExecuteMsg::WithdrawProtocolFees {
+      denom,
+      amount,
+      recipient,
+    } => execute_withdraw_protocol_fees(deps, env, info, denom, amount, recipient),
+
+    ExecuteMsg::Nominate { candidate } => execute_nominate(deps, env, info, candidate),
+    ExecuteMsg::Vote { candidate } => execute_vote(deps, env, info, candidate),
+    ExecuteMsg::TallyElection {} => execute_tally_election(deps, env, info),
+    ExecuteMsg::FinalizeElection {} => execute_finalize_election(deps, env, info),
+
+    ExecuteMsg::GrantRole { address, role } => execute_grant_role(deps, env, info, address, role),
+    ExecuteMsg::RevokeRole { address, role } => execute_revoke_role(deps, env, info, address, role),
+
+    ExecuteMsg::SetGuardian { guardian } => execute_set_guardian(deps, env, info, guardian),
+    ExecuteMsg::Pause { action, reason } => execute_pause(deps, env, info, action, reason),
+    ExecuteMsg::Unpause { action } => execute_unpause(deps, env, info, action),
+
+    ExecuteMsg::SetCouncil { members, threshold } => execute_set_council(deps, env, info, members, threshold),
+    ExecuteMsg::CouncilPropose { action, expires_at } => execute_council_propose(deps, env, info, *action, expires_at),
+    ExecuteMsg::CouncilApprove { proposal_id } => execute_council_approve(deps, env, info, proposal_id),
+    ExecuteMsg::CouncilRevokeApproval { proposal_id } => execute_council_revoke_approval(deps, env, info, proposal_id),
+    ExecuteMsg::CouncilExecute { proposal_id } => execute_council_execute(deps, env, info, proposal_id),
 
     ExecuteMsg::RegisterCity { name, power_level } => execute_register_city(deps, env, info, name, power_level),
+    ExecuteMsg::UpdateCity {
+      city_id,
+      name,
+      power_level,
+    } => execute_update_city(deps, env, info, city_id, name, power_level),
+    ExecuteMsg::SetMayor { city_id, mayor } => execute_set_mayor(deps, env, info, city_id, mayor),
+    ExecuteMsg::AppointDeputy { city_id, deputy } => execute_appoint_deputy(deps, env, info, city_id, deputy),
+    ExecuteMsg::DismissDeputy { city_id, deputy } => execute_dismiss_deputy(deps, env, info, city_id, deputy),
+    ExecuteMsg::KickFromCity {
+      city_id,
+      person,
+      reason,
+    } => execute_kick_from_city(deps, env, info, city_id, person, reason),
+    ExecuteMsg::BanFromCity { city_id, person, until } => {
+      execute_ban_from_city(deps, env, info, city_id, person, until)
+    }
+    ExecuteMsg::UnbanFromCity { city_id, person } => execute_unban_from_city(deps, env, info, city_id, person),
+    ExecuteMsg::SetCityProfile { city_id, profile } => execute_set_city_profile(deps, env, info, city_id, profile),
+    ExecuteMsg::SetCityAgeLimits {
+      city_id,
+      min_age,
+      max_age,
+    } => execute_set_city_age_limits(deps, env, info, city_id, min_age, max_age),
+    ExecuteMsg::EvictOverage {
+      city_id,
+      start_id,
+      limit,
+    } => execute_evict_overage(deps, env, info, city_id, start_id, limit),
+    ExecuteMsg::SetCityCapacity { city_id, max_members } => {
+      execute_set_city_capacity(deps, env, info, city_id, max_members)
+    }
+    ExecuteMsg::SetJoinPolicy { city_id, join_policy } => {
+      execute_set_join_policy(deps, env, info, city_id, join_policy)
+    }
+    ExecuteMsg::InviteToCity { city_id, person } => execute_invite_to_city(deps, env, info, city_id, person),
+    ExecuteMsg::RevokeInvite { city_id, person } => execute_revoke_invite(deps, env, info, city_id, person),
+    ExecuteMsg::CreateInviteCode {
+      city_id,
+      code_hash,
+      expires_at,
+      uses,
+    } => execute_create_invite_code(deps, env, info, city_id, code_hash, expires_at, uses),
+    ExecuteMsg::RevokeInviteCode { city_id, code_hash } => {
+      execute_revoke_invite_code(deps, env, info, city_id, code_hash)
+    }
+    ExecuteMsg::ApproveJoinRequest { city_id, person } => {
+      execute_approve_join_request(deps, env, info, city_id, person)
+    }
+    ExecuteMsg::RejectJoinRequest { city_id, person } => execute_reject_join_request(deps, env, info, city_id, person),
+    ExecuteMsg::ArchiveCity { city_id } => execute_archive_city(deps, env, info, city_id),
+    ExecuteMsg::DeleteCity { city_id } => execute_delete_city(deps, env, info, city_id),
+    ExecuteMsg::MergeCities { from, into } => execute_merge_cities(deps, env, info, from, into),
+    ExecuteMsg::IndexCityNames {} => execute_index_city_names(deps, env, info),
+    ExecuteMsg::SetJoinFee { city_id, join_fee } => execute_set_join_fee(deps, env, info, city_id, join_fee),
+    ExecuteMsg::SetCw20JoinFee { city_id, token, amount } => {
+      execute_set_cw20_join_fee(deps, env, info, city_id, token, amount)
+    }
+    ExecuteMsg::WithdrawCityTreasury {
+      city_id,
+      denom,
+      amount,
+      recipient,
+    } => execute_withdraw_city_treasury(deps, env, info, city_id, denom, amount, recipient),
+    ExecuteMsg::CreateRegion { name, parent_id } => execute_create_region(deps, env, info, name, parent_id),
+    ExecuteMsg::UpdateRegion {
+      region_id,
+      name,
+      min_age,
+      max_age,
+    } => execute_update_region(deps, env, info, region_id, name, min_age, max_age),
+    ExecuteMsg::RemoveRegion { region_id } => execute_remove_region(deps, env, info, region_id),
+    ExecuteMsg::SetCityRegion { city_id, region_id } => execute_set_city_region(deps, env, info, city_id, region_id),
     ExecuteMsg::RegisterPerson {
       birthday,
       nickname,
@@ -47,18 +732,29 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> R
     } => execute_register_person(deps, env, info, birthday, nickname, email),
     ExecuteMsg::UpdatePerson { nickname, email } => execute_update_person(deps, env, info, nickname, email),
 
-    ExecuteMsg::RegisterInCity { city_id } => execute_register_in_city(deps, env, info, city_id),
+    ExecuteMsg::RegisterInCity { city_id, invite_code } => {
+      execute_register_in_city(deps, env, info, city_id, invite_code)
+    }
     ExecuteMsg::UnregisterFromCity { city_id } => execute_unregister_from_city(deps, env, info, city_id),
+    ExecuteMsg::LeaveWaitlist { city_id } => execute_leave_waitlist(deps, env, info, city_id),
+    ExecuteMsg::CancelJoinRequest { city_id } => execute_cancel_join_request(deps, env, info, city_id),
+    ExecuteMsg::ClaimRefund { denom } => execute_claim_refund(deps, env, info, denom),
+    ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
   }
 }
 
 fn execute_become_maintainer(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
   // Get config from storage
-  let mut config = get_storage(deps.storage)?;
+  let config = get_storage(deps.storage)?;
+
+  // Maintainer role could not be claimed in frozen mode
+  if config.frozen {
+    return Err(ContractError::Frozen {});
+  }
 
   // Check current maintainer is not caller
   let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
-  if canonical_sender == config.maintainer {
+  if config.maintainer == Some(canonical_sender.clone()) {
     return Err(ContractError::AlreadyMaintainer {});
   }
 
@@ -66,506 +762,8265 @@ fn execute_become_maintainer(deps: DepsMut, env: Env, info: MessageInfo) -> Resu
   let person = get_person(deps.storage, canonical_sender.clone())?;
 
   // Check caller name
-  if person.nickname != MAINTAINER_REQUIREMENT_NAME {
+  if person.nickname != config.maintainer_requirement_name {
     return Err(ContractError::InconsistentMaintainer {
       requirement: "You are not crazy enough".to_string(),
     });
   }
 
   // Check caller age
-  if (1970 * YEAR_IN_SECONDS + env.block.time.seconds())
-    < MAINTAINER_REQUIREMENT_AGE as u64 * YEAR_IN_SECONDS
-      + person.birthday.year as u64 * YEAR_IN_SECONDS
-      + person.birthday.day.unwrap_or(366) as u64 * DAY_IN_SECONDS
-  {
+  if person.birthday.age_at(env.block.time) < config.maintainer_requirement_age as u64 {
     return Err(ContractError::InconsistentMaintainer {
       requirement: "You are too young".to_string(),
     });
   }
 
+  // Check there is no pending claim
+  if may_get_maintainer_claim(deps.storage)?.is_some() {
+    return Err(ContractError::MaintainerClaimPending {});
+  }
+
+  // Store claim, maintainer is able to veto it during challenge window
+  set_maintainer_claim(
+    deps.storage,
+    &MaintainerClaim {
+      claimant: canonical_sender,
+      finalizable_at: Expiration::AtTime(env.block.time.plus_seconds(config.maintainer_claim_window)),
+    },
+  )?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_veto_maintainer_claim(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Get claim and check challenge window is not over
+  let claim = get_maintainer_claim(deps.storage)?;
+  if claim.finalizable_at.is_expired(&env.block) {
+    return Err(ContractError::ChallengeWindowOver {});
+  }
+
+  // Remove claim
+  remove_maintainer_claim(deps.storage);
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_finalize_maintainer_claim(deps: DepsMut, env: Env, _: MessageInfo) -> Result<Response, ContractError> {
+  // Get config and claim from storage
+  let mut config = get_storage(deps.storage)?;
+  let claim = get_maintainer_claim(deps.storage)?;
+
+  // Check challenge window is over
+  if !claim.finalizable_at.is_expired(&env.block) {
+    return Err(ContractError::ChallengeWindowNotOver {});
+  }
+
   // Update config
-  config.maintainer = canonical_sender;
+  config.maintainer = Some(claim.claimant);
 
   // Store config to cold storage
   set_storage(deps.storage, &config)?;
 
+  // Claim is fulfilled and proposal of previous maintainer is not relevant anymore
+  remove_maintainer_claim(deps.storage);
+  remove_maintainer_proposal(deps.storage);
+
   // Return default Ok response
   Ok(Response::default())
 }
 
-fn execute_register_city(
+fn execute_propose_maintainer(
   deps: DepsMut,
-  _: Env,
+  env: Env,
   info: MessageInfo,
-  name: CityName,
-  power_level: u8,
+  address: Addr,
+  expires_at: Expiration,
 ) -> Result<Response, ContractError> {
   // Get config from storage
   let config = get_storage(deps.storage)?;
 
-  // Check caller is maintainer
-  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
-  if canonical_sender != config.maintainer {
-    return Err(ContractError::Unauthorized {});
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Check candidate is not current maintainer
+  let candidate = deps.api.addr_canonicalize(address.as_str())?;
+  if config.maintainer == Some(candidate.clone()) {
+    return Err(ContractError::AlreadyMaintainer {});
   }
 
-  // Register new city
-  create_city(deps.storage, name, power_level)?;
+  // Validate expiration
+  if expires_at.is_expired(&env.block) {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Store proposal, previous one is overwritten
+  set_maintainer_proposal(deps.storage, &MaintainerProposal { candidate, expires_at })?;
 
   // Return default Ok response
   Ok(Response::default())
 }
 
-fn execute_register_person(
-  deps: DepsMut,
-  env: Env,
-  info: MessageInfo,
-  birthday: Birthday,
-  nickname: Nickname,
-  email: Option<Email>,
-) -> Result<Response, ContractError> {
-  // Validate birthday day
-  if let Some(day) = birthday.day {
-    if day > 366 || day == 0 {
-      return Err(ContractError::InconsistentData {});
-    }
+fn execute_accept_maintainer(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+  // Get config and proposal from storage
+  let mut config = get_storage(deps.storage)?;
+  let proposal = get_maintainer_proposal(deps.storage)?;
+
+  // Check caller is proposed maintainer
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  if canonical_sender != proposal.candidate {
+    return Err(ContractError::NotMaintainerCandidate {});
   }
 
-  // Validate birthday year
-  if birthday.year < 1756 || birthday.year > (env.block.time.seconds() / YEAR_IN_SECONDS) as u16 + 1970 {
-    return Err(ContractError::InconsistentData {});
+  // Check proposal is not expired
+  if proposal.expires_at.is_expired(&env.block) {
+    return Err(ContractError::MaintainerProposalExpired {});
   }
 
-  // Check person is not registered yet
-  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
-  create_person(deps.storage, canonical_sender, birthday, nickname, email)?;
+  // Update config
+  config.maintainer = Some(canonical_sender);
+
+  // Store config to cold storage
+  set_storage(deps.storage, &config)?;
+
+  // Proposal is fulfilled
+  remove_maintainer_proposal(deps.storage);
 
   // Return default Ok response
   Ok(Response::default())
 }
 
-fn execute_update_person(
-  deps: DepsMut,
-  _: Env,
-  info: MessageInfo,
-  nickname: Nickname,
-  email: Option<Email>,
-) -> Result<Response, ContractError> {
-  // Update person by address
-  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
-  update_person(deps.storage, canonical_sender, nickname, email)?;
+fn execute_cancel_maintainer_proposal(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Check proposal exists
+  get_maintainer_proposal(deps.storage)?;
+
+  // Remove proposal
+  remove_maintainer_proposal(deps.storage);
 
   // Return default Ok response
   Ok(Response::default())
 }
 
-fn execute_register_in_city(deps: DepsMut, _: Env, info: MessageInfo, city_id: u64) -> Result<Response, ContractError> {
-  // Create link betwee user and city
-  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
-  register_in_city(deps.storage, canonical_sender, city_id)?;
+fn execute_renounce_maintainer(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Clear maintainer and guardian forever
+  let mut config = get_storage(deps.storage)?;
+  config.maintainer = None;
+  config.guardian = None;
+  config.frozen = true;
+
+  // Store config to cold storage
+  set_storage(deps.storage, &config)?;
+
+  // Pending handover, claim and council are not relevant anymore
+  remove_maintainer_proposal(deps.storage);
+  remove_maintainer_claim(deps.storage);
+  remove_council(deps.storage);
+
+  // Nobody is able to unpause actions anymore
+  remove_pause_reason(deps.storage);
+  clear_action_pause_reasons(deps.storage)?;
 
   // Return default Ok response
   Ok(Response::default())
 }
 
-fn execute_unregister_from_city(
+#[allow(clippy::too_many_arguments)]
+fn execute_update_config(
   deps: DepsMut,
-  _: Env,
+  env: Env,
   info: MessageInfo,
-  city_id: u64,
+  maintainer_requirement_name: Option<Nickname>,
+  maintainer_requirement_age: Option<u8>,
+  election_voting_period: Option<u64>,
+  election_quorum: Option<u64>,
+  max_page_size: Option<u64>,
+  city_deletion_batch_size: Option<u64>,
+  join_request_period: Option<u64>,
+  protocol_fee_share: Option<u64>,
 ) -> Result<Response, ContractError> {
-  // Remove link betwee user and city
-  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
-  unregister_from_city(deps.storage, canonical_sender, city_id)?;
+  // Get config from storage
+  let mut config = get_storage(deps.storage)?;
+
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Update provided fields only
+  if let Some(name) = maintainer_requirement_name {
+    config.maintainer_requirement_name = name;
+  }
+  if let Some(age) = maintainer_requirement_age {
+    config.maintainer_requirement_age = age;
+  }
+  if let Some(period) = election_voting_period {
+    if period == 0 {
+      return Err(ContractError::InconsistentData {});
+    }
+    config.election_voting_period = period;
+  }
+  if let Some(quorum) = election_quorum {
+    config.election_quorum = quorum;
+  }
+  if let Some(size) = max_page_size {
+    if size == 0 {
+      return Err(ContractError::InconsistentData {});
+    }
+    config.max_page_size = size;
+  }
+  if let Some(size) = city_deletion_batch_size {
+    if size == 0 {
+      return Err(ContractError::InconsistentData {});
+    }
+    config.city_deletion_batch_size = size;
+  }
+  if let Some(period) = join_request_period {
+    if period == 0 {
+      return Err(ContractError::InconsistentData {});
+    }
+    config.join_request_period = period;
+  }
+  if let Some(share) = protocol_fee_share {
+    if share > BASIS_POINTS {
+      return Err(ContractError::InconsistentData {});
+    }
+    config.protocol_fee_share = share;
+  }
+
+  // Store config to cold storage
+  set_storage(deps.storage, &config)?;
 
   // Return default Ok response
   Ok(Response::default())
 }
 
-// Query contract entrypoint
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
-  // Route call to corresponding method
-  match msg {
-    QueryMsg::LookMaintainer {} => Ok(to_binary(&query_look_maintainer(deps)?)?),
-    QueryMsg::LookPerson { person } => Ok(to_binary(&query_look_person(deps, person)?)?),
-    QueryMsg::LookCities { start_id, limit } => Ok(to_binary(&query_look_cities(deps, start_id, limit)?)?),
-    QueryMsg::LookPersonCities {
-      person,
-      start_id,
-      limit,
-    } => Ok(to_binary(&query_look_person_cities(deps, person, start_id, limit)?)?),
-    QueryMsg::LookCityPeople { city, start_id, limit } => {
-      Ok(to_binary(&query_look_city_people(deps, city, start_id, limit)?)?)
+fn execute_withdraw_protocol_fees(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  denom: Denom,
+  amount: Option<Uint128>,
+  recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Withdraw from protocol treasury to recipient or caller
+  let recipient = deps.api.addr_validate(recipient.unwrap_or(info.sender).as_str())?;
+  let withdrawn = match &denom {
+    Denom::Native(denom) => withdraw_protocol_balance(deps.storage, denom, amount)?,
+    Denom::Cw20(token) => {
+      let canonical_token = deps.api.addr_canonicalize(token.as_str())?;
+      withdraw_protocol_cw20_balance(deps.storage, &canonical_token, amount)?
     }
-  }
+  };
+
+  // Return Ok response with transfer of withdrawn funds
+  Ok(Response::new().add_message(transfer_msg(&denom, withdrawn, &recipient)?))
 }
 
-fn query_look_maintainer(deps: Deps) -> Result<ResponseMsg, ContractError> {
-  // Get config from storage
-  let config = get_storage(deps.storage)?;
+fn execute_nominate(deps: DepsMut, env: Env, info: MessageInfo, candidate: Addr) -> Result<Response, ContractError> {
+  // Maintainer role could not be elected in frozen mode
+  if get_storage(deps.storage)?.frozen {
+    return Err(ContractError::Frozen {});
+  }
 
-  // Return corresponding responce
-  Ok(ResponseMsg::LookMaintainer {
-    maintainer: deps.api.addr_humanize(&config.maintainer)?,
-  })
-}
+  // Check caller and candidate are registered persons
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  get_person(deps.storage, canonical_sender)?;
+  let canonical_candidate = deps.api.addr_canonicalize(candidate.as_str())?;
+  get_person(deps.storage, canonical_candidate.clone())?;
 
-fn query_look_person(deps: Deps, addr: Addr) -> Result<ResponseMsg, ContractError> {
-  // Get person by address
-  let canonical_sender = deps.api.addr_canonicalize(addr.as_str())?;
-  let person = get_person(deps.storage, canonical_sender.clone())?;
+  // Get current election or start the next one
+  let mut election = match may_get_election(deps.storage)? {
+    Some(election) if election.status != ElectionStatus::Finalized => {
+      if election.status != ElectionStatus::Voting || election.ends_at.is_expired(&env.block) {
+        return Err(ContractError::VotingClosed {});
+      }
 
-  // Return corresponding responce
-  Ok(ResponseMsg::LookPerson {
-    person: PersonResponse {
-      address: addr,
-      birthday: person.birthday,
-      nickname: person.nickname,
-      email: person.email,
-      resident_times: person.cities_count,
-    },
-  })
-}
+      election
+    }
+    _ => {
+      // Get config from storage
+      let mut config = get_storage(deps.storage)?;
 
-fn query_look_cities(deps: Deps, start_id: u64, limit: u64) -> Result<ResponseMsg, ContractError> {
-  // Get config from storage
-  let config = get_storage(deps.storage)?;
+      let election = Election {
+        id: config.elections_count,
+        status: ElectionStatus::Voting,
+        ends_at: Expiration::AtTime(env.block.time.plus_seconds(config.election_voting_period)),
+        quorum: config.election_quorum,
+        candidates_count: 0,
+        total_weight: 0,
+        winner: None,
+      };
 
-  // Init result
-  let mut result: Vec<CityResponse> = vec![];
+      // Update elections counter
+      config.elections_count += 1;
+      set_storage(deps.storage, &config)?;
 
-  // Loop through ids
-  for i in start_id..(start_id + limit) {
-    // Validate id
-    if config.cities_count > i {
-      // Get city by id
-      let city = get_city(deps.storage, i)?;
-      // Update result
-      result.push(CityResponse {
-        id: i,
-        name: city.city_name,
-        power_level: city.power_level,
-        population: city.members_count,
-      })
-    } else {
-      break;
+      election
     }
+  };
+
+  // Check candidate is not nominated yet
+  if may_get_candidate_votes(deps.storage, election.id, &canonical_candidate)?.is_some() {
+    return Err(ContractError::AlreadyNominated {});
   }
 
-  // Return corresponding responce
-  Ok(ResponseMsg::LookCities { cities: result })
+  // Tally iterates over all candidates, so their number is limited
+  if election.candidates_count >= MAX_ELECTION_CANDIDATES {
+    return Err(ContractError::TooManyCandidates {
+      max: MAX_ELECTION_CANDIDATES,
+    });
+  }
+
+  // Store nominated candidate and election
+  election.candidates_count += 1;
+  set_candidate_votes(deps.storage, election.id, &canonical_candidate, 0)?;
+  set_election(deps.storage, &election)?;
+
+  // Return Ok response with election id
+  Ok(Response::new().add_attribute("election_id", election.id.to_string()))
 }
 
-fn query_look_person_cities(deps: Deps, addr: Addr, start_id: u64, limit: u64) -> Result<ResponseMsg, ContractError> {
-  // Get person by address
-  let canonical_sender = deps.api.addr_canonicalize(addr.as_str())?;
-  let person = get_person(deps.storage, canonical_sender.clone())?;
+fn execute_vote(deps: DepsMut, env: Env, info: MessageInfo, candidate: Addr) -> Result<Response, ContractError> {
+  // Get election and check voting period is not over
+  let mut election = get_election(deps.storage)?;
+  if election.status != ElectionStatus::Voting || election.ends_at.is_expired(&env.block) {
+    return Err(ContractError::VotingClosed {});
+  }
 
-  // Init result
-  let mut result: Vec<CityResponse> = vec![];
+  // Vote weight is number of cities where caller is registered
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  let weight = get_person(deps.storage, canonical_sender.clone())?.cities_count;
+  if weight == 0 {
+    return Err(ContractError::NoVotingPower {});
+  }
 
-  // Loop through ids
-  for i in start_id..(start_id + limit) {
-    // Validate id
-    if person.cities_count > i {
-      // Get city id
-      let city_id = get_city_id_by_person(deps.storage, canonical_sender.clone(), i)?;
-      // Get city by id
-      let city = get_city(deps.storage, city_id)?;
-      // Update result
-      result.push(CityResponse {
-        id: city_id,
-        name: city.city_name,
-        power_level: city.power_level,
-        population: city.members_count,
-      })
-    } else {
-      break;
-    }
+  // Check candidate is nominated
+  let canonical_candidate = deps.api.addr_canonicalize(candidate.as_str())?;
+  if may_get_candidate_votes(deps.storage, election.id, &canonical_candidate)?.is_none() {
+    return Err(ContractError::NotNominated {});
   }
 
-  // Return corresponding responce
-  Ok(ResponseMsg::LookPersonCities { cities: result })
-}
+  // Withdraw previous vote of caller
+  if let Some(vote) = may_get_vote(deps.storage, election.id, &canonical_sender)? {
+    let votes = may_get_candidate_votes(deps.storage, election.id, &vote.candidate)?.unwrap_or_default();
+    set_candidate_votes(deps.storage, election.id, &vote.candidate, votes - vote.weight)?;
+    election.total_weight -= vote.weight;
+  }
 
-fn query_look_city_people(deps: Deps, city_id: u64, start_id: u64, limit: u64) -> Result<ResponseMsg, ContractError> {
-  // Get person by id
-  let city = get_city(deps.storage, city_id)?;
+  // Store new vote
+  let votes = may_get_candidate_votes(deps.storage, election.id, &canonical_candidate)?.unwrap_or_default();
+  set_candidate_votes(deps.storage, election.id, &canonical_candidate, votes + weight)?;
+  set_vote(
+    deps.storage,
+    election.id,
+    &canonical_sender,
+    &Vote {
+      candidate: canonical_candidate,
+      weight,
+    },
+  )?;
 
-  // Init result
-  let mut result: Vec<PersonResponse> = vec![];
+  // Store updated election
+  election.total_weight += weight;
+  set_election(deps.storage, &election)?;
 
-  // Loop through ids
-  for i in start_id..(start_id + limit) {
-    // Validate id
-    if city.members_count > i {
-      // Get person address
-      let addr = get_person_address_by_city(deps.storage, city_id, i)?;
-      // Get person by address
-      let person = get_person(deps.storage, addr.clone())?;
-      // Update result
-      result.push(PersonResponse {
-        address: deps.api.addr_humanize(&addr)?,
-        birthday: person.birthday,
-        nickname: person.nickname,
-        email: person.email,
-        resident_times: person.cities_count,
-      })
-    } else {
-      break;
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_tally_election(deps: DepsMut, env: Env, _: MessageInfo) -> Result<Response, ContractError> {
+  // Get election and check voting period is over
+  let mut election = get_election(deps.storage)?;
+  if election.status != ElectionStatus::Voting {
+    return Err(ContractError::ElectionAlreadyTallied {});
+  }
+  if !election.ends_at.is_expired(&env.block) {
+    return Err(ContractError::VotingNotOver {});
+  }
+
+  // Find candidate with the most votes, tie results in no winner
+  let mut winner: Option<CanonicalAddr> = None;
+  let mut winner_votes: u64 = 0;
+  for item in get_candidates(deps.storage, election.id, None) {
+    let (candidate, votes) = item?;
+    if votes > winner_votes {
+      winner = Some(candidate);
+      winner_votes = votes;
+    } else if votes == winner_votes {
+      winner = None;
     }
   }
 
-  // Return corresponding responce
-  Ok(ResponseMsg::LookCityPeople { people: result })
+  // Winner is elected only if quorum is reached
+  if election.total_weight < election.quorum {
+    winner = None;
+  }
+
+  // Store tallied election
+  election.status = ElectionStatus::Tallied;
+  election.winner = winner;
+  set_election(deps.storage, &election)?;
+
+  // Return default Ok response
+  Ok(Response::default())
 }
 
-#[cfg(test)]
-mod tests {
+fn execute_finalize_election(deps: DepsMut, _: Env, _: MessageInfo) -> Result<Response, ContractError> {
+  // Get election and check it is tallied
+  let mut election = get_election(deps.storage)?;
+  if election.status != ElectionStatus::Tallied {
+    return Err(ContractError::ElectionNotTallied {});
+  }
 
-  use crate::error::wrap_not_found;
+  // Maintainer role could not be elected in frozen mode
+  let mut config = get_storage(deps.storage)?;
+  if config.frozen {
+    return Err(ContractError::Frozen {});
+  }
 
-  use super::*;
-  use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-  use cosmwasm_std::{from_binary, Addr, DivideByZeroError};
-  use cosmwasm_std::{StdError, Timestamp};
+  // Move maintainer role to the winner
+  if let Some(winner) = election.winner.clone() {
+    config.maintainer = Some(winner);
+    set_storage(deps.storage, &config)?;
 
-  #[test]
-  fn check_error_wrapper() {
-    match wrap_not_found(Ok("data")) {
-      Ok(data) => assert_eq!(data, "data"),
-      Err(_) => unreachable!(),
-    }
+    // Proposal of previous maintainer is not relevant anymore
+    remove_maintainer_proposal(deps.storage);
+  }
 
-    match wrap_not_found(Ok(())) {
-      Ok(data) => assert_eq!(data, ()),
-      Err(_) => unreachable!(),
-    }
+  // Store finalized election
+  election.status = ElectionStatus::Finalized;
+  set_election(deps.storage, &election)?;
 
-    match wrap_not_found::<()>(Err(StdError::NotFound {
-      kind: "kind".to_string(),
-    })) {
-      Ok(_) => unreachable!(),
-      Err(err) => match err {
-        ContractError::NotFound { kind } => assert_eq!(kind, "kind".to_string()),
-        _ => unreachable!(),
-      },
-    }
+  // Return default Ok response
+  Ok(Response::default())
+}
 
-    match wrap_not_found::<()>(Err(StdError::DivideByZero {
-      source: DivideByZeroError {
-        operand: "ohh".to_string(),
-      },
-    })) {
-      Ok(_) => unreachable!(),
-      Err(err) => match err {
-        ContractError::Std(stderr) => match stderr {
-          StdError::DivideByZero { source } => assert_eq!(source.operand, "ohh".to_string()),
-          _ => unreachable!(),
-        },
-        _ => unreachable!(),
-      },
-    }
-  }
+fn execute_grant_role(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  address: Addr,
+  role: Role,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
 
-  #[test]
-  fn initialization() {
-    // Get deps for calls
-    let mut deps = mock_dependencies();
+  // Store granted role
+  let canonical_address = deps.api.addr_canonicalize(address.as_str())?;
+  grant_role(deps.storage, &canonical_address, role)?;
 
-    // Setup env and info
-    let mut env = mock_env();
-    env.block.height = 887;
-    env.block.time = Timestamp::from_seconds(3);
-    let info = mock_info("creator", &[]);
+  // Return default Ok response
+  Ok(Response::default())
+}
 
-    // Instantiate and check response
-    let res = instantiate(deps.as_mut(), env, info, InstantiateMsg {}).unwrap();
-    assert_eq!(0, res.messages.len());
+fn execute_revoke_role(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  address: Addr,
+  role: Role,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
 
-    // Query and check result
-    let maintainer: ResponseMsg =
-      from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+  // Check the role is granted
+  let canonical_address = deps.api.addr_canonicalize(address.as_str())?;
+  if !has_role(deps.storage, &canonical_address, role) {
+    return Err(ContractError::MissingRole {
+      role: role.key().to_string(),
+    });
+  }
+
+  // Remove granted role
+  revoke_role(deps.storage, &canonical_address, role);
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_set_guardian(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  guardian: Option<Addr>,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Update config
+  let mut config = get_storage(deps.storage)?;
+  config.guardian = match guardian {
+    Some(guardian) => Some(deps.api.addr_canonicalize(guardian.as_str())?),
+    None => None,
+  };
+
+  // Store config to cold storage
+  set_storage(deps.storage, &config)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_pause(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  action: Option<String>,
+  reason: String,
+) -> Result<Response, ContractError> {
+  // Check caller is guardian or maintainer
+  check_guardian(deps.as_ref(), &env, &info)?;
+
+  // Check paused action is known and able to be paused
+  if let Some(action) = &action {
+    if !PAUSABLE_ACTIONS.contains(&action.as_str()) {
+      return Err(ContractError::InconsistentData {});
+    }
+  }
+
+  // Pause specific action or the whole execute entrypoint
+  match action {
+    Some(action) => set_action_pause_reason(deps.storage, &action, &reason)?,
+    None => set_pause_reason(deps.storage, &reason)?,
+  }
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_unpause(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  action: Option<String>,
+) -> Result<Response, ContractError> {
+  // Check caller is guardian or maintainer
+  check_guardian(deps.as_ref(), &env, &info)?;
+
+  // Unpause specific action or the whole execute entrypoint
+  match action {
+    Some(action) => {
+      if may_get_action_pause_reason(deps.storage, &action)?.is_none() {
+        return Err(ContractError::NotPaused { action });
+      }
+      remove_action_pause_reason(deps.storage, &action);
+    }
+    None => {
+      if may_get_pause_reason(deps.storage)?.is_none() {
+        return Err(ContractError::NotPaused {
+          action: "execute".to_string(),
+        });
+      }
+      remove_pause_reason(deps.storage);
+    }
+  }
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_set_council(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  members: Vec<Addr>,
+  threshold: u64,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Empty members list disables council mode
+  if members.is_empty() {
+    remove_council(deps.storage);
+
+    return Ok(Response::default());
+  }
+
+  // Validate members are unique
+  let mut canonical_members: Vec<CanonicalAddr> = vec![];
+  for member in members {
+    let canonical_member = deps.api.addr_canonicalize(member.as_str())?;
+    if canonical_members.contains(&canonical_member) {
+      return Err(ContractError::InconsistentData {});
+    }
+    canonical_members.push(canonical_member);
+  }
+
+  // Validate threshold is reachable
+  if threshold == 0 || threshold > canonical_members.len() as u64 {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Store council to cold storage
+  set_council(
+    deps.storage,
+    &Council {
+      members: canonical_members,
+      threshold,
+    },
+  )?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_council_propose(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  action: ExecuteMsg,
+  expires_at: Expiration,
+) -> Result<Response, ContractError> {
+  // Check caller is council member
+  let (_, canonical_sender) = check_council_member(deps.as_ref(), &info)?;
+
+  // Only privileged actions need council approval
+  if !action.is_privileged() {
+    return Err(ContractError::NotPrivilegedAction {});
+  }
+
+  // Validate expiration
+  if expires_at.is_expired(&env.block) {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Store proposal, it is approved by proposer
+  let proposal_id = create_council_proposal(
+    deps.storage,
+    &CouncilProposal {
+      proposer: canonical_sender.clone(),
+      action,
+      expires_at,
+      approvals: vec![canonical_sender],
+    },
+  )?;
+
+  // Return Ok response with proposal id
+  Ok(Response::new().add_attribute("proposal_id", proposal_id.to_string()))
+}
+
+fn execute_council_approve(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  proposal_id: u64,
+) -> Result<Response, ContractError> {
+  // Check caller is council member
+  let (_, canonical_sender) = check_council_member(deps.as_ref(), &info)?;
+
+  // Get proposal and check it is not expired
+  let mut proposal = get_council_proposal(deps.storage, proposal_id)?;
+  if proposal.expires_at.is_expired(&env.block) {
+    return Err(ContractError::CouncilProposalExpired {});
+  }
+
+  // Check proposal is not approved by caller yet
+  if proposal.approvals.contains(&canonical_sender) {
+    return Err(ContractError::AlreadyApproved {});
+  }
+
+  // Store updated proposal
+  proposal.approvals.push(canonical_sender);
+  set_council_proposal(deps.storage, proposal_id, &proposal)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_council_revoke_approval(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  proposal_id: u64,
+) -> Result<Response, ContractError> {
+  // Check caller is council member
+  let (_, canonical_sender) = check_council_member(deps.as_ref(), &info)?;
+
+  // Get proposal and check it is not expired
+  let mut proposal = get_council_proposal(deps.storage, proposal_id)?;
+  if proposal.expires_at.is_expired(&env.block) {
+    return Err(ContractError::CouncilProposalExpired {});
+  }
+
+  // Check proposal is approved by caller
+  if !proposal.approvals.contains(&canonical_sender) {
+    return Err(ContractError::NotApproved {});
+  }
+
+  // Store updated proposal
+  proposal.approvals.retain(|member| member != &canonical_sender);
+  set_council_proposal(deps.storage, proposal_id, &proposal)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_council_execute(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  proposal_id: u64,
+) -> Result<Response, ContractError> {
+  // Check caller is council member
+  let (council, _) = check_council_member(deps.as_ref(), &info)?;
+
+  // Get proposal and check it is not expired
+  let proposal = get_council_proposal(deps.storage, proposal_id)?;
+  if proposal.expires_at.is_expired(&env.block) {
+    return Err(ContractError::CouncilProposalExpired {});
+  }
+
+  // Count approvals of current council members only
+  let approvals = proposal
+    .approvals
+    .iter()
+    .filter(|member| council.members.contains(member))
+    .count() as u64;
+  if approvals < council.threshold {
+    return Err(ContractError::NotEnoughApprovals {
+      approvals,
+      threshold: council.threshold,
+    });
+  }
+
+  // Proposal is fulfilled
+  remove_council_proposal(deps.storage, proposal_id);
+
+  // Execute action on behalf of the contract
+  let contract_info = MessageInfo {
+    sender: env.contract.address.clone(),
+    funds: vec![],
+  };
+  execute(deps, env, contract_info, proposal.action)
+}
+
+fn execute_register_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  name: CityName,
+  power_level: u8,
+) -> Result<Response, ContractError> {
+  // Check caller is city creator
+  check_role(deps.as_ref(), &env, &info, Role::CityCreator)?;
+
+  // Validate city name
+  validate_city_name(&name)?;
+
+  // Register new city
+  create_city(deps.storage, name, power_level)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_update_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  name: CityName,
+  power_level: u8,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate city name
+  validate_city_name(&name)?;
+
+  // Update city metadata and record the change
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  let change_id = update_city(
+    deps.storage,
+    city_id,
+    name,
+    power_level,
+    canonical_sender,
+    env.block.time,
+  )?;
+
+  // Return Ok response with change id
+  Ok(Response::new().add_attribute("change_id", change_id.to_string()))
+}
+
+fn execute_set_mayor(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  mayor: Option<Addr>,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Store city mayor
+  let mayor = match mayor {
+    Some(mayor) => Some(deps.api.addr_canonicalize(mayor.as_str())?),
+    None => None,
+  };
+  set_mayor(deps.storage, city_id, mayor)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_appoint_deputy(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  deputy: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city mayor
+  let city = get_city(deps.storage, city_id)?;
+  check_mayor(deps.as_ref(), &env, &info, city_id)?;
+
+  // Check number of deputies
+  if city.deputies_count >= MAX_CITY_DEPUTIES {
+    return Err(ContractError::TooManyDeputies { max: MAX_CITY_DEPUTIES });
+  }
+
+  // Store deputy
+  let canonical_deputy = deps.api.addr_canonicalize(deputy.as_str())?;
+  appoint_deputy(deps.storage, city_id, &canonical_deputy)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_dismiss_deputy(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  deputy: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city mayor
+  get_city(deps.storage, city_id)?;
+  check_mayor(deps.as_ref(), &env, &info, city_id)?;
+
+  // Remove deputy
+  let canonical_deputy = deps.api.addr_canonicalize(deputy.as_str())?;
+  dismiss_deputy(deps.storage, city_id, &canonical_deputy)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_kick_from_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+  reason: Option<String>,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin or moderator
+  get_city(deps.storage, city_id)?;
+  check_city_moderator(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate kick reason
+  if let Some(reason) = &reason {
+    if reason.trim().is_empty() || reason.len() > MAX_KICK_REASON_LENGTH {
+      return Err(ContractError::InconsistentData {});
+    }
+  }
+
+  // Remove link between person and city
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  unregister_from_city(deps.storage, env.block.time, canonical_person, city_id)?;
+
+  // Return Ok response with kick reason
+  let mut response = Response::new();
+  if let Some(reason) = reason {
+    response = response.add_attribute("reason", reason);
+  }
+  Ok(response)
+}
+
+fn execute_ban_from_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+  until: Expiration,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin or moderator
+  get_city(deps.storage, city_id)?;
+  check_city_moderator(deps.as_ref(), &env, &info, city_id)?;
+
+  // Check ban is not expired
+  if until.is_expired(&env.block) {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Remove person from the city and store the ban
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  ban_from_city(
+    deps.storage,
+    env.block.time,
+    city_id,
+    &canonical_person,
+    &CityBan { until },
+  )?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_unban_from_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin or moderator
+  get_city(deps.storage, city_id)?;
+  check_city_moderator(deps.as_ref(), &env, &info, city_id)?;
+
+  // Remove the ban
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  remove_city_ban(deps.storage, city_id, &canonical_person);
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_set_city_profile(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  profile: CityProfile,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate every provided field
+  validate_city_profile(&env, &profile)?;
+
+  // Store optional city metadata, missed fields are cleared
+  set_city_profile(deps.storage, city_id, profile)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_set_city_age_limits(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  min_age: Option<u8>,
+  max_age: Option<u8>,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate age limits
+  if let (Some(min_age), Some(max_age)) = (min_age, max_age) {
+    if min_age > max_age {
+      return Err(ContractError::InconsistentData {});
+    }
+  }
+
+  // Store age limits, they are applied to joining people and eviction sweep
+  set_city_age_limits(deps.storage, city_id, min_age, max_age)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_evict_overage(
+  deps: DepsMut,
+  env: Env,
+  _: MessageInfo,
+  city_id: u64,
+  start_id: u64,
+  limit: u64,
+) -> Result<Response, ContractError> {
+  // Unregister members over maximal age, anyone is able to run the sweep
+  let (evicted, next_id) = evict_overage_batch(
+    deps.storage,
+    env.block.time,
+    city_id,
+    start_id,
+    limit.min(MAX_EVICTION_SCAN),
+  )?;
+
+  // Return Ok response with number of evicted members and identifier to continue from
+  Ok(
+    Response::new()
+      .add_attribute("evicted", evicted.to_string())
+      .add_attribute("next_id", next_id.to_string()),
+  )
+}
+
+fn execute_set_city_capacity(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  max_members: Option<u64>,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate capacity
+  if max_members == Some(0) {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Store capacity and admit waiting people to free slots
+  set_city_capacity(deps.storage, city_id, max_members)?;
+  let admitted = admit_from_waitlist(deps.storage, env.block.time, city_id, MAX_WAITLIST_ADMISSIONS)?;
+
+  // Return Ok response with number of admitted people
+  Ok(Response::new().add_attribute("admitted", admitted.to_string()))
+}
+
+fn execute_set_join_policy(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  join_policy: JoinPolicy,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Store join policy
+  set_join_policy(deps.storage, city_id, join_policy)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_invite_to_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Store invite
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  set_city_invite(deps.storage, city_id, &canonical_person)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_revoke_invite(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Remove invite
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  remove_city_invite(deps.storage, city_id, &canonical_person);
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_create_invite_code(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  code_hash: String,
+  expires_at: Expiration,
+  uses: u64,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate code hash, expiration and number of uses
+  let code_hash = decode_code_hash(&code_hash)?;
+  if expires_at.is_expired(&env.block) || uses == 0 {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Store invite code hash, the secret code is never stored
+  set_invite_code(
+    deps.storage,
+    city_id,
+    &code_hash,
+    &InviteCode {
+      expires_at,
+      remaining_uses: uses,
+    },
+  )?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_revoke_invite_code(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  code_hash: String,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Remove invite code
+  let code_hash = decode_code_hash(&code_hash)?;
+  remove_invite_code(deps.storage, city_id, &code_hash);
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_approve_join_request(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Check join request is not expired
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  let request = get_join_request(deps.storage, city_id, &canonical_person)?;
+  if request.expires_at.is_expired(&env.block) {
+    return Err(ContractError::JoinRequestExpired {});
+  }
+
+  // Remove join request and create link between person and city or put the person on waitlist
+  remove_join_request(deps.storage, city_id, &canonical_person);
+  let registered = join_city(deps.storage, env.block.time, canonical_person.clone(), city_id)?;
+  if registered {
+    settle_join_fee(deps.storage, city_id, &canonical_person, true)?;
+  }
+
+  // Return Ok response with join status
+  Ok(Response::new().add_attribute("status", join_status(registered)))
+}
+
+fn execute_reject_join_request(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  person: Addr,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Remove join request and refund join fee
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  get_join_request(deps.storage, city_id, &canonical_person)?;
+  remove_join_request(deps.storage, city_id, &canonical_person);
+  settle_join_fee(deps.storage, city_id, &canonical_person, false)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_archive_city(deps: DepsMut, env: Env, info: MessageInfo, city_id: u64) -> Result<Response, ContractError> {
+  // Check caller is city admin or moderator
+  let city = get_city(deps.storage, city_id)?;
+  check_city_moderator(deps.as_ref(), &env, &info, city_id)?;
+
+  // Check city is active and no other city is being merged into it
+  if city.status != CityStatus::Active {
+    return Err(ContractError::CityArchived {
+      city_name: city.city_name,
+    });
+  }
+  if city.merges_in_progress > 0 {
+    return Err(ContractError::CityMergeInProgress {
+      city_name: city.city_name,
+    });
+  }
+
+  // Store city status
+  set_city_status(deps.storage, city_id, CityStatus::Archived)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_delete_city(deps: DepsMut, env: Env, info: MessageInfo, city_id: u64) -> Result<Response, ContractError> {
+  // Get config and city from storage
+  let config = get_storage(deps.storage)?;
+  let city = get_city(deps.storage, city_id)?;
+
+  // Deletion is started by maintainer, then anyone is able to continue it
+  match city.status {
+    CityStatus::Active => {
+      return Err(ContractError::CityNotArchived {
+        city_name: city.city_name,
+      })
+    }
+    CityStatus::Archived if city.merges_in_progress > 0 => {
+      return Err(ContractError::CityMergeInProgress {
+        city_name: city.city_name,
+      })
+    }
+    CityStatus::Archived => {
+      check_maintainer(deps.as_ref(), &env, &info)?;
+      set_city_status(deps.storage, city_id, CityStatus::Deleting)?;
+    }
+    CityStatus::Deleting => {}
+    CityStatus::Merging => {
+      return Err(ContractError::CityMerged {
+        city_name: city.city_name,
+      })
+    }
+  }
+
+  // Unregister batch of city members
+  let (members_left, deleted) =
+    delete_city_batch(deps.storage, env.block.time, city_id, config.city_deletion_batch_size)?;
+
+  // Return Ok response with number of members left and deletion completion flag
+  Ok(
+    Response::new()
+      .add_attribute("members_left", members_left.to_string())
+      .add_attribute("deleted", deleted.to_string()),
+  )
+}
+
+fn execute_merge_cities(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  from: u64,
+  into: u64,
+) -> Result<Response, ContractError> {
+  // Get config and merged city from storage
+  let config = get_storage(deps.storage)?;
+  let city = get_city(deps.storage, from)?;
+
+  // Merge is started by maintainer, then anyone is able to continue it
+  let starting = match city.status {
+    CityStatus::Active | CityStatus::Archived if city.merged_into.is_none() => {
+      check_maintainer(deps.as_ref(), &env, &info)?;
+
+      // Check no other city is being merged into the merged city
+      if city.merges_in_progress > 0 {
+        return Err(ContractError::CityMergeInProgress {
+          city_name: city.city_name,
+        });
+      }
+      if from == into {
+        return Err(ContractError::InconsistentData {});
+      }
+
+      true
+    }
+    CityStatus::Merging if city.merged_into == Some(into) => false,
+    CityStatus::Deleting => {
+      return Err(ContractError::CityArchived {
+        city_name: city.city_name,
+      })
+    }
+    _ => {
+      return Err(ContractError::CityMerged {
+        city_name: city.city_name,
+      })
+    }
+  };
+
+  // Check the city merged into is active on every batch
+  let target = get_city(deps.storage, into)?;
+  if target.status != CityStatus::Active {
+    return Err(ContractError::CityArchived {
+      city_name: target.city_name,
+    });
+  }
+  if starting {
+    // Members are moved regardless of capacity, so the city merged into should have slots for all of them
+    if let Some(max_members) = target.max_members {
+      if target.members_count + city.members_count > max_members {
+        return Err(ContractError::CityCapacityExceeded {
+          city_name: target.city_name,
+        });
+      }
+    }
+
+    start_city_merge(deps.storage, from, into)?;
+  }
+
+  // Move batch of city members
+  let members_left = merge_city_batch(deps.storage, &env.block, from, into, config.city_deletion_batch_size)?;
+
+  // Return Ok response with number of members left
+  Ok(Response::new().add_attribute("members_left", members_left.to_string()))
+}
+
+fn execute_index_city_names(deps: DepsMut, _: Env, _: MessageInfo) -> Result<Response, ContractError> {
+  // Index batch of migrated city names, anyone is able to continue indexing
+  let indexed = index_city_names_batch(deps.storage, MAX_CITY_NAMES_INDEXED)?;
+
+  // Return Ok response with indexing completion flag
+  Ok(Response::new().add_attribute("indexed", indexed.to_string()))
+}
+
+fn execute_set_join_fee(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  join_fee: Option<Coin>,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate join fee
+  if let Some(join_fee) = &join_fee {
+    if join_fee.amount.is_zero() || join_fee.denom.is_empty() {
+      return Err(ContractError::InconsistentData {});
+    }
+  }
+
+  // Store join fee, it is applied to people joining the city from now on
+  set_join_fee(deps.storage, city_id, join_fee)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_set_cw20_join_fee(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  token: Addr,
+  amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+  // Check city exists and caller is city admin
+  get_city(deps.storage, city_id)?;
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Validate join fee
+  if amount == Some(Uint128::zero()) {
+    return Err(ContractError::InconsistentData {});
+  }
+
+  // Store join fee in the token, missed amount removes the token from accepted ones
+  let canonical_token = deps.api.addr_canonicalize(token.as_str())?;
+  set_cw20_join_fee(deps.storage, city_id, &canonical_token, amount)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_withdraw_city_treasury(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  denom: Denom,
+  amount: Option<Uint128>,
+  recipient: Option<Addr>,
+) -> Result<Response, ContractError> {
+  // Check caller is city admin, treasury of deleted city is withdrawn by maintainer
+  check_city_admin(deps.as_ref(), &env, &info, city_id)?;
+
+  // Withdraw from city treasury to recipient or caller
+  let recipient = deps.api.addr_validate(recipient.unwrap_or(info.sender).as_str())?;
+  let withdrawn = match &denom {
+    Denom::Native(denom) => withdraw_city_balance(deps.storage, city_id, denom, amount)?,
+    Denom::Cw20(token) => {
+      let canonical_token = deps.api.addr_canonicalize(token.as_str())?;
+      withdraw_city_cw20_balance(deps.storage, city_id, &canonical_token, amount)?
+    }
+  };
+
+  // Return Ok response with transfer of withdrawn funds
+  Ok(Response::new().add_message(transfer_msg(&denom, withdrawn, &recipient)?))
+}
+
+fn execute_create_region(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  name: RegionName,
+  parent_id: Option<u64>,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Validate region name, it follows city name rules
+  validate_city_name(&name)?;
+
+  // Create country or region of the country
+  let region_id = create_region(deps.storage, name, parent_id)?;
+
+  // Return Ok response with region id
+  Ok(Response::new().add_attribute("region_id", region_id.to_string()))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_update_region(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  region_id: u64,
+  name: RegionName,
+  min_age: Option<u8>,
+  max_age: Option<u8>,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Validate region name and age limits
+  validate_city_name(&name)?;
+  if let (Some(min_age), Some(max_age)) = (min_age, max_age) {
+    if min_age > max_age {
+      return Err(ContractError::InconsistentData {});
+    }
+  }
+
+  // Store region metadata and age limits inherited by its cities
+  update_region(deps.storage, region_id, name, min_age, max_age)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_remove_region(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  region_id: u64,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Remove region without child regions and cities
+  remove_region(deps.storage, region_id)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_set_city_region(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  region_id: Option<u64>,
+) -> Result<Response, ContractError> {
+  // Check caller has maintainer access level
+  check_maintainer(deps.as_ref(), &env, &info)?;
+
+  // Move city with its population to the region
+  set_city_region(deps.storage, city_id, region_id)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_register_person(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  birthday: Birthday,
+  nickname: Nickname,
+  email: Option<Email>,
+) -> Result<Response, ContractError> {
+  // Validate birthday
+  validate_birthday(&env, &birthday)?;
+
+  // Check person is not registered yet
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  create_person(deps.storage, canonical_sender, birthday, nickname, email)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_update_person(
+  deps: DepsMut,
+  _: Env,
+  info: MessageInfo,
+  nickname: Nickname,
+  email: Option<Email>,
+) -> Result<Response, ContractError> {
+  // Update person by address
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  update_person(deps.storage, canonical_sender, nickname, email)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_register_in_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+  invite_code: Option<String>,
+) -> Result<Response, ContractError> {
+  // Get city from storage
+  let city = get_city(deps.storage, city_id)?;
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+  // City accepting cw20 tokens only is joined via cw20 Receive hook
+  if city.join_fee.is_none() && has_cw20_join_fees(deps.storage, city_id) {
+    return Err(ContractError::NativeFeeNotAccepted {});
+  }
+
+  // Check funds cover join fee, overpayment is refunded to caller
+  let (join_fee, refund) = pay_join_fee(&info, &city.join_fee)?;
+
+  // Join fee is kept until the person is registered, then it is credited to city treasury
+  if let Some(join_fee) = &join_fee {
+    escrow_join_fee(deps.storage, city_id, &canonical_sender, join_fee)?;
+  }
+
+  enter_city(deps, &env, canonical_sender, city, city_id, invite_code).map(|response| response.add_messages(refund))
+}
+
+// Helper function for registering person in city according to its join policy once join fee is paid
+fn enter_city(
+  deps: DepsMut,
+  env: &Env,
+  addr: CanonicalAddr,
+  city: City,
+  city_id: u64,
+  invite_code: Option<String>,
+) -> Result<Response, ContractError> {
+  // Banned person is not able to join the city until the ban expires, expired ban is removed
+  if let Some(ban) = may_get_city_ban(deps.storage, city_id, &addr)? {
+    if !ban.until.is_expired(&env.block) {
+      return Err(ContractError::BannedFromCity {
+        city_name: city.city_name,
+        until: ban.until,
+      });
+    }
+    remove_city_ban(deps.storage, city_id, &addr);
+  }
+
+  // Invited person joins the city regardless of join policy, the invite is used once
+  let invited = match invite_code {
+    Some(code) => {
+      let code_hash = Sha256::digest(code.as_bytes());
+      use_invite_code(deps.storage, &env.block, city_id, &code_hash)?;
+      true
+    }
+    None => {
+      let invited = has_city_invite(deps.storage, city_id, &addr);
+      if invited {
+        remove_city_invite(deps.storage, city_id, &addr);
+      }
+      invited
+    }
+  };
+
+  match city.join_policy {
+    JoinPolicy::ApprovalRequired if !invited => {
+      // Create join request approved by city admin
+      let request = JoinRequest {
+        expires_at: Expiration::AtTime(
+          env
+            .block
+            .time
+            .plus_seconds(get_storage(deps.storage)?.join_request_period),
+        ),
+      };
+      create_join_request(deps.storage, &env.block, addr, city_id, &request)?;
+
+      // Return Ok response with join status
+      Ok(Response::new().add_attribute("status", "requested"))
+    }
+    JoinPolicy::InviteOnly if !invited => Err(ContractError::NotInvited {
+      city_name: city.city_name,
+    }),
+    _ => {
+      // Create link betwee user and city or put the user on waitlist
+      let registered = join_city(deps.storage, env.block.time, addr.clone(), city_id)?;
+      if registered {
+        settle_join_fee(deps.storage, city_id, &addr, true)?;
+      }
+
+      // Return Ok response with join status
+      Ok(Response::new().add_attribute("status", join_status(registered)))
+    }
+  }
+}
+
+fn execute_receive(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+  // Caller is cw20 token contract, tokens are sent by person
+  let sender = deps.api.addr_validate(&wrapper.sender)?;
+  let canonical_sender = deps.api.addr_canonicalize(sender.as_str())?;
+
+  let (city_id, invite_code) = match from_binary(&wrapper.msg)? {
+    ReceiveMsg::JoinCity { city_id, invite_code } => {
+      // Check the embedded action is not paused
+      check_not_paused(
+        deps.as_ref(),
+        &ExecuteMsg::RegisterInCity {
+          city_id,
+          invite_code: invite_code.clone(),
+        },
+      )?;
+
+      (city_id, invite_code)
+    }
+    ReceiveMsg::RegisterPerson {
+      birthday,
+      nickname,
+      email,
+      city_id,
+    } => {
+      // Check the embedded actions are not paused
+      check_not_paused(
+        deps.as_ref(),
+        &ExecuteMsg::RegisterPerson {
+          birthday: birthday.clone(),
+          nickname: nickname.clone(),
+          email: email.clone(),
+        },
+      )?;
+      check_not_paused(
+        deps.as_ref(),
+        &ExecuteMsg::RegisterInCity {
+          city_id,
+          invite_code: None,
+        },
+      )?;
+
+      // Create person before joining the city
+      validate_birthday(&env, &birthday)?;
+      create_person(deps.storage, canonical_sender.clone(), birthday, nickname, email)?;
+
+      (city_id, None)
+    }
+  };
+
+  // Check the token is accepted by city and sent amount covers join fee
+  let city = get_city(deps.storage, city_id)?;
+  let canonical_token = deps.api.addr_canonicalize(info.sender.as_str())?;
+  let join_fee =
+    may_get_cw20_join_fee(deps.storage, city_id, &canonical_token)?.ok_or(ContractError::TokenNotAccepted {})?;
+  if wrapper.amount < join_fee {
+    return Err(ContractError::InsufficientFee { required: join_fee });
+  }
+
+  // Join fee is kept until the person is registered, overpayment is refunded to sender
+  escrow_cw20_join_fee(deps.storage, city_id, &canonical_sender, &canonical_token, join_fee)?;
+  let refund = match wrapper.amount - join_fee {
+    overpaid if overpaid.is_zero() => None,
+    overpaid => Some(transfer_msg(&Denom::Cw20(info.sender), overpaid, &sender)?),
+  };
+
+  enter_city(deps, &env, canonical_sender, city, city_id, invite_code).map(|response| response.add_messages(refund))
+}
+
+fn execute_leave_waitlist(deps: DepsMut, _: Env, info: MessageInfo, city_id: u64) -> Result<Response, ContractError> {
+  // Remove caller from city waitlist and refund join fee
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  leave_waitlist(deps.storage, &canonical_sender, city_id)?;
+  settle_join_fee(deps.storage, city_id, &canonical_sender, false)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_cancel_join_request(
+  deps: DepsMut,
+  _: Env,
+  info: MessageInfo,
+  city_id: u64,
+) -> Result<Response, ContractError> {
+  // Remove join request of caller and refund join fee
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  get_join_request(deps.storage, city_id, &canonical_sender)?;
+  remove_join_request(deps.storage, city_id, &canonical_sender);
+  settle_join_fee(deps.storage, city_id, &canonical_sender, false)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+fn execute_claim_refund(deps: DepsMut, _: Env, info: MessageInfo, denom: Denom) -> Result<Response, ContractError> {
+  // Withdraw refunded join fees of caller
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  let refund = match &denom {
+    Denom::Native(denom) => withdraw_refund(deps.storage, &canonical_sender, denom)?,
+    Denom::Cw20(token) => {
+      let canonical_token = deps.api.addr_canonicalize(token.as_str())?;
+      withdraw_cw20_refund(deps.storage, &canonical_sender, &canonical_token)?
+    }
+  };
+
+  // Return Ok response with transfer of refunded funds
+  Ok(Response::new().add_message(transfer_msg(&denom, refund, &info.sender)?))
+}
+
+fn execute_unregister_from_city(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  city_id: u64,
+) -> Result<Response, ContractError> {
+  // Remove link betwee user and city
+  let canonical_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
+  unregister_from_city(deps.storage, env.block.time, canonical_sender, city_id)?;
+
+  // Return default Ok response
+  Ok(Response::default())
+}
+
+// Query contract entrypoint
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+  // Route call to corresponding method
+  match msg {
+    QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
+    QueryMsg::LookMaintainer {} => Ok(to_binary(&query_look_maintainer(deps)?)?),
+    QueryMsg::LookMaintainerClaim {} => Ok(to_binary(&query_look_maintainer_claim(deps)?)?),
+    QueryMsg::LookMaintainerProposal {} => Ok(to_binary(&query_look_maintainer_proposal(deps)?)?),
+    QueryMsg::LookElection {} => Ok(to_binary(&query_look_election(deps)?)?),
+    QueryMsg::LookElectionResults { start_after, limit } => {
+      Ok(to_binary(&query_look_election_results(deps, start_after, limit)?)?)
+    }
+    QueryMsg::LookCouncil {} => Ok(to_binary(&query_look_council(deps)?)?),
+    QueryMsg::LookCouncilProposals { start_after, limit } => Ok(to_binary(&query_look_council_proposals(
+      deps,
+      env,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookRoles { address } => Ok(to_binary(&query_look_roles(deps, address)?)?),
+    QueryMsg::LookRoleHolders {
+      role,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_role_holders(deps, role, start_after, limit)?)?),
+    QueryMsg::LookPauses {} => Ok(to_binary(&query_look_pauses(deps)?)?),
+    QueryMsg::LookPerson { person } => Ok(to_binary(&query_look_person(deps, person)?)?),
+    QueryMsg::LookCities { start_id, limit } => Ok(to_binary(&query_look_cities(deps, start_id, limit)?)?),
+    QueryMsg::LookCityByName { name } => Ok(to_binary(&query_look_city_by_name(deps, name)?)?),
+    QueryMsg::CitiesInArea {
+      min_lat,
+      max_lat,
+      min_lon,
+      max_lon,
+      limit,
+      start_after,
+    } => Ok(to_binary(&query_cities_in_area(
+      deps,
+      Coordinates {
+        latitude: min_lat,
+        longitude: min_lon,
+      },
+      Coordinates {
+        latitude: max_lat,
+        longitude: max_lon,
+      },
+      limit,
+      start_after,
+    )?)?),
+    QueryMsg::NearestCities {
+      latitude,
+      longitude,
+      limit,
+    } => Ok(to_binary(&query_nearest_cities(
+      deps,
+      Coordinates { latitude, longitude },
+      limit,
+    )?)?),
+    QueryMsg::LookCityHistory {
+      city_id,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_city_history(deps, city_id, start_after, limit)?)?),
+    QueryMsg::LookMayor { city_id } => Ok(to_binary(&query_look_mayor(deps, city_id)?)?),
+    QueryMsg::LookCityDeputies {
+      city_id,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_city_deputies(
+      deps,
+      city_id,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookAdministeredCities {
+      administrator,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_administered_cities(
+      deps,
+      administrator,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookPersonCities {
+      person,
+      start_id,
+      limit,
+    } => Ok(to_binary(&query_look_person_cities(deps, person, start_id, limit)?)?),
+    QueryMsg::LookWaitlistPosition { city_id, person } => {
+      Ok(to_binary(&query_look_waitlist_position(deps, city_id, person)?)?)
+    }
+    QueryMsg::LookInviteCode { city_id, code_hash } => {
+      Ok(to_binary(&query_look_invite_code(deps, city_id, code_hash)?)?)
+    }
+    QueryMsg::LookCityJoinRequests {
+      city_id,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_city_join_requests(
+      deps,
+      city_id,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookCityBans {
+      city_id,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_city_bans(deps, city_id, start_after, limit)?)?),
+    QueryMsg::LookBanStatus { city_id, person } => Ok(to_binary(&query_look_ban_status(deps, env, city_id, person)?)?),
+    QueryMsg::LookPersonJoinRequests {
+      person,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_person_join_requests(
+      deps,
+      person,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookCityPeople { city, start_id, limit } => {
+      Ok(to_binary(&query_look_city_people(deps, city, start_id, limit)?)?)
+    }
+    QueryMsg::LookRegion { region_id } => Ok(to_binary(&query_look_region(deps, region_id)?)?),
+    QueryMsg::LookRegionChildren {
+      region_id,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_region_children(
+      deps,
+      region_id,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookRegionCities {
+      region_id,
+      start_after,
+      limit,
+    } => Ok(to_binary(&query_look_region_cities(
+      deps,
+      region_id,
+      start_after,
+      limit,
+    )?)?),
+    QueryMsg::LookRegionPath { region_id } => Ok(to_binary(&query_look_region_path(deps, region_id)?)?),
+    QueryMsg::LookCw20JoinFees { city_id } => Ok(to_binary(&query_look_cw20_join_fees(deps, city_id)?)?),
+    QueryMsg::LookCityTreasury { city_id } => Ok(to_binary(&query_look_city_treasury(deps, city_id)?)?),
+    QueryMsg::LookProtocolTreasury {} => Ok(to_binary(&query_look_protocol_treasury(deps)?)?),
+    QueryMsg::LookEscrowedJoinFee { city_id, person } => {
+      Ok(to_binary(&query_look_escrowed_join_fee(deps, city_id, person)?)?)
+    }
+    QueryMsg::LookRefunds { person } => Ok(to_binary(&query_look_refunds(deps, person)?)?),
+  }
+}
+
+fn query_config(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get config from storage
+  let config = get_storage(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::Config {
+    config: ConfigResponse {
+      maintainer: match config.maintainer {
+        Some(maintainer) => Some(deps.api.addr_humanize(&maintainer)?),
+        None => None,
+      },
+      frozen: config.frozen,
+      cities_count: config.cities_count,
+      council_proposals_count: config.council_proposals_count,
+      elections_count: config.elections_count,
+      regions_count: config.regions_count,
+      maintainer_requirement_name: config.maintainer_requirement_name,
+      maintainer_requirement_age: config.maintainer_requirement_age,
+      maintainer_claim_window: config.maintainer_claim_window,
+      election_voting_period: config.election_voting_period,
+      election_quorum: config.election_quorum,
+      max_page_size: config.max_page_size,
+      city_deletion_batch_size: config.city_deletion_batch_size,
+      join_request_period: config.join_request_period,
+      protocol_fee_share: config.protocol_fee_share,
+      guardian: match config.guardian {
+        Some(guardian) => Some(deps.api.addr_humanize(&guardian)?),
+        None => None,
+      },
+    },
+  })
+}
+
+fn query_look_maintainer(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get config from storage
+  let config = get_storage(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookMaintainer {
+    maintainer: match config.maintainer {
+      Some(maintainer) => Some(deps.api.addr_humanize(&maintainer)?),
+      None => None,
+    },
+    frozen: config.frozen,
+  })
+}
+
+fn query_look_maintainer_claim(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get pending claim from storage
+  let claim = may_get_maintainer_claim(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookMaintainerClaim {
+    claim: match claim {
+      Some(claim) => Some(MaintainerClaimResponse {
+        claimant: deps.api.addr_humanize(&claim.claimant)?,
+        finalizable_at: claim.finalizable_at,
+      }),
+      None => None,
+    },
+  })
+}
+
+fn query_look_maintainer_proposal(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get pending proposal from storage
+  let proposal = may_get_maintainer_proposal(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookMaintainerProposal {
+    proposal: match proposal {
+      Some(proposal) => Some(MaintainerProposalResponse {
+        candidate: deps.api.addr_humanize(&proposal.candidate)?,
+        expires_at: proposal.expires_at,
+      }),
+      None => None,
+    },
+  })
+}
+
+fn query_look_election(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get the latest election from storage
+  let election = may_get_election(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookElection {
+    election: match election {
+      Some(election) => Some(ElectionResponse {
+        id: election.id,
+        status: election.status,
+        ends_at: election.ends_at,
+        quorum: election.quorum,
+        total_weight: election.total_weight,
+        winner: match election.winner {
+          Some(winner) => Some(deps.api.addr_humanize(&winner)?),
+          None => None,
+        },
+      }),
+      None => None,
+    },
+  })
+}
+
+fn query_look_election_results(
+  deps: Deps,
+  start_after: Option<Addr>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Init result
+  let mut result: Vec<CandidateResponse> = vec![];
+
+  // There are no results without election
+  let election = match may_get_election(deps.storage)? {
+    Some(election) => election,
+    None => return Ok(ResponseMsg::LookElectionResults { candidates: result }),
+  };
+
+  // Validate start address
+  let start_after = match start_after {
+    Some(addr) => Some(deps.api.addr_canonicalize(addr.as_str())?),
+    None => None,
+  };
+
+  // Loop through candidates of the latest election
+  for item in get_candidates(deps.storage, election.id, start_after).take(limit as usize) {
+    let (candidate, votes) = item?;
+
+    // Update result
+    result.push(CandidateResponse {
+      candidate: deps.api.addr_humanize(&candidate)?,
+      votes,
+    })
+  }
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookElectionResults { candidates: result })
+}
+
+fn query_look_council(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get council from storage
+  let council = may_get_council(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCouncil {
+    council: match council {
+      Some(council) => Some(CouncilResponse {
+        members: council
+          .members
+          .iter()
+          .map(|member| deps.api.addr_humanize(member))
+          .collect::<StdResult<Vec<Addr>>>()?,
+        threshold: council.threshold,
+      }),
+      None => None,
+    },
+  })
+}
+
+fn query_look_council_proposals(
+  deps: Deps,
+  env: Env,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Init result
+  let mut result: Vec<CouncilProposalResponse> = vec![];
+
+  // Loop through pending proposals
+  for item in get_council_proposals(deps.storage, start_after) {
+    if result.len() as u64 >= limit {
+      break;
+    }
+
+    // Skip expired proposals
+    let (id, proposal) = item?;
+    if proposal.expires_at.is_expired(&env.block) {
+      continue;
+    }
+
+    // Update result
+    result.push(CouncilProposalResponse {
+      id,
+      proposer: deps.api.addr_humanize(&proposal.proposer)?,
+      action: proposal.action,
+      expires_at: proposal.expires_at,
+      approvals: proposal
+        .approvals
+        .iter()
+        .map(|member| deps.api.addr_humanize(member))
+        .collect::<StdResult<Vec<Addr>>>()?,
+    })
+  }
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCouncilProposals { proposals: result })
+}
+
+fn query_look_roles(deps: Deps, addr: Addr) -> Result<ResponseMsg, ContractError> {
+  // Get roles by address
+  let canonical_address = deps.api.addr_canonicalize(addr.as_str())?;
+  let roles = get_roles_by_address(deps.storage, &canonical_address)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRoles { roles })
+}
+
+fn query_look_role_holders(
+  deps: Deps,
+  role: Role,
+  start_after: Option<Addr>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Validate start address
+  let start_after = match start_after {
+    Some(addr) => Some(deps.api.addr_canonicalize(addr.as_str())?),
+    None => None,
+  };
+
+  // Get role holders
+  let holders = get_role_holders(deps.storage, role, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRoleHolders {
+    holders: holders
+      .iter()
+      .map(|holder| deps.api.addr_humanize(holder))
+      .collect::<StdResult<Vec<Addr>>>()?,
+  })
+}
+
+fn query_look_pauses(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Get pause reasons from storage
+  let reason = may_get_pause_reason(deps.storage)?;
+  let actions = get_action_pause_reasons(deps.storage)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookPauses {
+    reason,
+    actions: actions
+      .into_iter()
+      .map(|(action, reason)| PausedAction { action, reason })
+      .collect(),
+  })
+}
+
+fn query_look_person(deps: Deps, addr: Addr) -> Result<ResponseMsg, ContractError> {
+  // Get person by address
+  let canonical_sender = deps.api.addr_canonicalize(addr.as_str())?;
+  let person = get_person(deps.storage, canonical_sender.clone())?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookPerson {
+    person: PersonResponse {
+      address: addr,
+      birthday: person.birthday,
+      nickname: person.nickname,
+      email: person.email,
+      resident_times: person.cities_count,
+    },
+  })
+}
+
+fn query_look_cities(deps: Deps, start_id: u64, limit: u64) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Init result
+  let mut result: Vec<CityResponse> = vec![];
+
+  // Loop through existing cities, deleted ids are skipped
+  for item in get_cities(deps.storage, start_id).take(limit as usize) {
+    let (id, city) = item?;
+
+    // Update result
+    result.push(city_response(id, city))
+  }
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCities { cities: result })
+}
+
+fn query_look_city_by_name(deps: Deps, name: CityName) -> Result<ResponseMsg, ContractError> {
+  // Find city by normalized name, merged city is redirected to the city it was merged into
+  let city_id = may_get_city_id_by_name(deps.storage, &name)?.ok_or(ContractError::NotFound {
+    kind: "City".to_string(),
+  })?;
+  let city_id = resolve_city_id(deps.storage, city_id)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityByName {
+    city: city_response(city_id, get_city(deps.storage, city_id)?),
+  })
+}
+
+fn query_cities_in_area(
+  deps: Deps,
+  min: Coordinates,
+  max: Coordinates,
+  limit: u64,
+  start_after: Option<Binary>,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Validate area, it is not wrapped over antimeridian
+  validate_coordinates(&min)?;
+  validate_coordinates(&max)?;
+  if min.latitude > max.latitude || min.longitude > max.longitude {
+    return Err(ContractError::InvalidCoordinates {});
+  }
+
+  // Get cities within area using coordinates index, pages are keyed by position in the index
+  let (cities, next_key) = get_cities_in_area(deps.storage, &min, &max, start_after.map(Vec::from), limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::CitiesInArea {
+    cities: cities.into_iter().map(|(id, city)| city_response(id, city)).collect(),
+    next_key: next_key.map(Binary::from),
+  })
+}
+
+fn query_nearest_cities(deps: Deps, point: Coordinates, limit: u64) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Validate point
+  validate_coordinates(&point)?;
+
+  // Get nearest cities using coordinates index
+  let (cities, is_complete) = get_nearest_cities(deps.storage, &point, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::NearestCities {
+    cities: cities.into_iter().map(|(id, city)| city_response(id, city)).collect(),
+    is_complete,
+  })
+}
+
+fn query_look_city_history(
+  deps: Deps,
+  city_id: u64,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Check city exists
+  get_city(deps.storage, city_id)?;
+
+  // Init result
+  let mut result: Vec<CityChangeResponse> = vec![];
+
+  // Loop through city changes
+  for item in get_city_changes(deps.storage, city_id, start_after).take(limit as usize) {
+    let (id, change) = item?;
+
+    // Update result
+    result.push(CityChangeResponse {
+      id,
+      changed_by: deps.api.addr_humanize(&change.changed_by)?,
+      changed_at: change.changed_at,
+      previous_name: change.previous_name,
+      previous_power_level: change.previous_power_level,
+      name: change.name,
+      power_level: change.power_level,
+    })
+  }
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityHistory { changes: result })
+}
+
+fn query_look_mayor(deps: Deps, city_id: u64) -> Result<ResponseMsg, ContractError> {
+  // Get city by id
+  let city = get_city(deps.storage, city_id)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookMayor {
+    mayor: match city.mayor {
+      Some(mayor) => Some(deps.api.addr_humanize(&mayor)?),
+      None => None,
+    },
+  })
+}
+
+fn query_look_city_deputies(
+  deps: Deps,
+  city_id: u64,
+  start_after: Option<Addr>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Check city exists
+  get_city(deps.storage, city_id)?;
+
+  // Validate start address
+  let start_after = match start_after {
+    Some(addr) => Some(deps.api.addr_canonicalize(addr.as_str())?),
+    None => None,
+  };
+
+  // Get city deputies
+  let deputies = get_city_deputies(deps.storage, city_id, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityDeputies {
+    deputies: deputies
+      .iter()
+      .map(|deputy| deps.api.addr_humanize(deputy))
+      .collect::<StdResult<Vec<Addr>>>()?,
+  })
+}
+
+fn query_look_administered_cities(
+  deps: Deps,
+  administrator: Addr,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Get cities administered by address
+  let canonical_administrator = deps.api.addr_canonicalize(administrator.as_str())?;
+  let cities = get_administered_cities(deps.storage, &canonical_administrator, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookAdministeredCities {
+    cities: cities
+      .into_iter()
+      .map(|(city_id, office)| AdministeredCityResponse { city_id, office })
+      .collect(),
+  })
+}
+
+fn query_look_person_cities(deps: Deps, addr: Addr, start_id: u64, limit: u64) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Get person by address
+  let canonical_sender = deps.api.addr_canonicalize(addr.as_str())?;
+  let person = get_person(deps.storage, canonical_sender.clone())?;
+
+  // Init result
+  let mut result: Vec<CityResponse> = vec![];
+
+  // Loop through ids
+  for i in start_id..(start_id + limit) {
+    // Validate id
+    if person.cities_count > i {
+      // Get city id
+      let city_id = get_city_id_by_person(deps.storage, canonical_sender.clone(), i)?;
+      // Get city by id
+      let city = get_city(deps.storage, city_id)?;
+      // Update result
+      result.push(city_response(city_id, city))
+    } else {
+      break;
+    }
+  }
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookPersonCities { cities: result })
+}
+
+fn query_look_city_people(deps: Deps, city_id: u64, start_id: u64, limit: u64) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Get city by id, merged city is redirected to the city it was merged into
+  let city_id = resolve_city_id(deps.storage, city_id)?;
+  let city = get_city(deps.storage, city_id)?;
+
+  // Init result
+  let mut result: Vec<PersonResponse> = vec![];
+
+  // Loop through ids
+  for i in start_id..(start_id + limit) {
+    // Validate id
+    if city.members_count > i {
+      // Get person address
+      let addr = get_person_address_by_city(deps.storage, city_id, i)?;
+      // Get person by address
+      let person = get_person(deps.storage, addr.clone())?;
+      // Update result
+      result.push(PersonResponse {
+        address: deps.api.addr_humanize(&addr)?,
+        birthday: person.birthday,
+        nickname: person.nickname,
+        email: person.email,
+        resident_times: person.cities_count,
+      })
+    } else {
+      break;
+    }
+  }
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityPeople { people: result })
+}
+
+fn query_look_waitlist_position(deps: Deps, city_id: u64, person: Addr) -> Result<ResponseMsg, ContractError> {
+  // Get city by id
+  let city = get_city(deps.storage, city_id)?;
+
+  // Get number of people ahead of person
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  let position = may_get_waitlist_position(deps.storage, &canonical_person, city_id)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookWaitlistPosition {
+    position,
+    length: city.waitlist_count,
+  })
+}
+
+fn query_look_invite_code(deps: Deps, city_id: u64, code_hash: String) -> Result<ResponseMsg, ContractError> {
+  // Get invite code by hash
+  let invite_code = may_get_invite_code(deps.storage, city_id, &decode_code_hash(&code_hash)?)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookInviteCode {
+    invite_code: invite_code.map(|invite_code| InviteCodeResponse {
+      city_id,
+      code_hash,
+      expires_at: invite_code.expires_at,
+      remaining_uses: invite_code.remaining_uses,
+    }),
+  })
+}
+
+fn query_look_city_bans(
+  deps: Deps,
+  city_id: u64,
+  start_after: Option<Addr>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Validate start address
+  let start_after = match start_after {
+    Some(addr) => Some(deps.api.addr_canonicalize(addr.as_str())?),
+    None => None,
+  };
+
+  // Get city bans, expired bans are listed until the person joins the city or is unbanned
+  let bans = get_city_bans(deps.storage, city_id, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityBans {
+    bans: bans
+      .into_iter()
+      .map(|(person, ban)| {
+        Ok(CityBanResponse {
+          person: deps.api.addr_humanize(&person)?,
+          until: ban.until,
+        })
+      })
+      .collect::<Result<Vec<CityBanResponse>, ContractError>>()?,
+  })
+}
+
+fn query_look_ban_status(deps: Deps, env: Env, city_id: u64, person: Addr) -> Result<ResponseMsg, ContractError> {
+  // Get the ban of person
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  let ban = may_get_city_ban(deps.storage, city_id, &canonical_person)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookBanStatus {
+    banned: ban.as_ref().map_or(false, |ban| !ban.until.is_expired(&env.block)),
+    until: ban.map(|ban| ban.until),
+  })
+}
+
+fn query_look_city_join_requests(
+  deps: Deps,
+  city_id: u64,
+  start_after: Option<Addr>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Check city exists
+  get_city(deps.storage, city_id)?;
+
+  // Validate start address
+  let start_after = match start_after {
+    Some(addr) => Some(deps.api.addr_canonicalize(addr.as_str())?),
+    None => None,
+  };
+
+  // Get city join requests
+  let requests = get_city_join_requests(deps.storage, city_id, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityJoinRequests {
+    requests: requests
+      .into_iter()
+      .map(|(person, request)| {
+        Ok(JoinRequestResponse {
+          city_id,
+          person: deps.api.addr_humanize(&person)?,
+          expires_at: request.expires_at,
+        })
+      })
+      .collect::<StdResult<Vec<JoinRequestResponse>>>()?,
+  })
+}
+
+fn query_look_person_join_requests(
+  deps: Deps,
+  person: Addr,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Get person join requests
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+  let requests = get_person_join_requests(deps.storage, &canonical_person, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookPersonJoinRequests {
+    requests: requests
+      .into_iter()
+      .map(|(city_id, request)| JoinRequestResponse {
+        city_id,
+        person: person.clone(),
+        expires_at: request.expires_at,
+      })
+      .collect(),
+  })
+}
+
+fn query_look_region(deps: Deps, region_id: u64) -> Result<ResponseMsg, ContractError> {
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRegion {
+    region: region_response(region_id, get_region(deps.storage, region_id)?),
+  })
+}
+
+fn query_look_region_children(
+  deps: Deps,
+  region_id: Option<u64>,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Get regions of country or countries
+  let regions = get_child_regions(deps.storage, region_id, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRegionChildren {
+    regions: regions
+      .into_iter()
+      .map(|(id, region)| region_response(id, region))
+      .collect(),
+  })
+}
+
+fn query_look_region_cities(
+  deps: Deps,
+  region_id: u64,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<ResponseMsg, ContractError> {
+  // Limit page size
+  let limit = page_limit(deps, limit)?;
+
+  // Get cities of region
+  let cities = get_region_cities(deps.storage, region_id, start_after, limit)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRegionCities {
+    cities: cities.into_iter().map(|(id, city)| city_response(id, city)).collect(),
+  })
+}
+
+fn query_look_region_path(deps: Deps, region_id: u64) -> Result<ResponseMsg, ContractError> {
+  // Get region and regions containing it up to country
+  let path = get_region_path(deps.storage, region_id)?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRegionPath {
+    path: path
+      .into_iter()
+      .map(|(id, region)| region_response(id, region))
+      .collect(),
+  })
+}
+
+fn query_look_cw20_join_fees(deps: Deps, city_id: u64) -> Result<ResponseMsg, ContractError> {
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCw20JoinFees {
+    join_fees: cw20_coins(deps, get_cw20_join_fees(deps.storage, city_id)?)?,
+  })
+}
+
+fn query_look_city_treasury(deps: Deps, city_id: u64) -> Result<ResponseMsg, ContractError> {
+  // Return corresponding responce
+  Ok(ResponseMsg::LookCityTreasury {
+    balances: get_city_balances(deps.storage, city_id)?,
+    cw20_balances: cw20_coins(deps, get_city_cw20_balances(deps.storage, city_id)?)?,
+  })
+}
+
+fn query_look_protocol_treasury(deps: Deps) -> Result<ResponseMsg, ContractError> {
+  // Return corresponding responce
+  Ok(ResponseMsg::LookProtocolTreasury {
+    balances: get_protocol_balances(deps.storage)?,
+    cw20_balances: cw20_coins(deps, get_protocol_cw20_balances(deps.storage)?)?,
+  })
+}
+
+fn query_look_escrowed_join_fee(deps: Deps, city_id: u64, person: Addr) -> Result<ResponseMsg, ContractError> {
+  // Get join fee kept for person
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookEscrowedJoinFee {
+    join_fee: may_get_escrowed_join_fee(deps.storage, city_id, &canonical_person)?,
+    cw20_join_fee: match may_get_escrowed_cw20_join_fee(deps.storage, city_id, &canonical_person)? {
+      Some((token, amount)) => Some(Cw20Coin {
+        address: deps.api.addr_humanize(&token)?.to_string(),
+        amount,
+      }),
+      None => None,
+    },
+  })
+}
+
+fn query_look_refunds(deps: Deps, person: Addr) -> Result<ResponseMsg, ContractError> {
+  // Get refund balances of person
+  let canonical_person = deps.api.addr_canonicalize(person.as_str())?;
+
+  // Return corresponding responce
+  Ok(ResponseMsg::LookRefunds {
+    refunds: get_refunds(deps.storage, &canonical_person)?,
+    cw20_refunds: cw20_coins(deps, get_cw20_refunds(deps.storage, &canonical_person)?)?,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+
+  use crate::error::wrap_not_found;
+  use crate::utils::{CitySeed, LegacyConfig};
+  use cw2::get_contract_version;
+  use cw_storage_plus::{Item, Map};
+
+  use super::*;
+  use crate::utils::{Coordinates, FoundingDate, RegionKind};
+  use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+  use cosmwasm_std::{coin, coins, Addr, DivideByZeroError};
+  use cosmwasm_std::{StdError, Timestamp};
+
+  fn mock_instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+      maintainer: None,
+      maintainer_requirement_name: Some("Super_Maintainer_887".to_string()),
+      maintainer_requirement_age: Some(17),
+      maintainer_claim_window: Some(DAY_IN_SECONDS),
+      election_voting_period: Some(3600),
+      election_quorum: Some(2),
+      max_page_size: None,
+      city_deletion_batch_size: None,
+      join_request_period: None,
+      protocol_fee_share: None,
+      seed_cities: None,
+    }
+  }
+
+  #[test]
+  #[allow(clippy::collapsible_match)]
+  fn check_error_wrapper() {
+    match wrap_not_found(Ok("data")) {
+      Ok(data) => assert_eq!(data, "data"),
+      Err(_) => unreachable!(),
+    }
+
+    match wrap_not_found(Ok(())) {
+      Ok(data) => assert_eq!(data, ()),
+      Err(_) => unreachable!(),
+    }
+
+    match wrap_not_found::<()>(Err(StdError::NotFound {
+      kind: "kind".to_string(),
+    })) {
+      Ok(_) => unreachable!(),
+      Err(err) => match err {
+        ContractError::NotFound { kind } => assert_eq!(kind, "kind".to_string()),
+        _ => unreachable!(),
+      },
+    }
+
+    match wrap_not_found::<()>(Err(StdError::DivideByZero {
+      source: DivideByZeroError {
+        operand: "ohh".to_string(),
+      },
+    })) {
+      Ok(_) => unreachable!(),
+      Err(err) => match err {
+        ContractError::Std(stderr) => match stderr {
+          StdError::DivideByZero { source } => assert_eq!(source.operand, "ohh".to_string()),
+          _ => unreachable!(),
+        },
+        _ => unreachable!(),
+      },
+    }
+  }
+
+  #[test]
+  fn initialization() {
+    // Get deps for calls
+    let mut deps = mock_dependencies();
+
+    // Setup env and info
+    let mut env = mock_env();
+    env.block.height = 887;
+    env.block.time = Timestamp::from_seconds(3);
+    let info = mock_info("creator", &[]);
+
+    // Instantiate and check response
+    let res = instantiate(deps.as_mut(), env, info, mock_instantiate_msg()).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    // Query and check result
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("creator")),
+        frozen: false,
+      }
+    );
+  }
+
+  #[test]
+  fn rich_initialization() {
+    let mut deps = mock_dependencies();
+
+    let bad_instantiation = instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      InstantiateMsg {
+        max_page_size: Some(0),
+        city_deletion_batch_size: None,
+        join_request_period: None,
+        protocol_fee_share: None,
+        ..mock_instantiate_msg()
+      },
+    );
+    assert!(bad_instantiation.is_err());
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      InstantiateMsg {
+        maintainer: Some(Addr::unchecked("owner")),
+        maintainer_requirement_name: None,
+        maintainer_requirement_age: None,
+        maintainer_claim_window: None,
+        election_voting_period: None,
+        election_quorum: None,
+        max_page_size: Some(2),
+        city_deletion_batch_size: None,
+        join_request_period: None,
+        protocol_fee_share: None,
+        seed_cities: Some(vec![
+          CitySeed {
+            name: "Super City".to_string(),
+            power_level: 3,
+          },
+          CitySeed {
+            name: "Secret City".to_string(),
+            power_level: 5,
+          },
+          CitySeed {
+            name: "Sleeping City".to_string(),
+            power_level: 1,
+          },
+        ]),
+      },
+    )
+    .unwrap();
+
+    let config: ResponseMsg = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+      config,
+      ResponseMsg::Config {
+        config: ConfigResponse {
+          maintainer: Some(Addr::unchecked("owner")),
+          frozen: false,
+          cities_count: 3,
+          council_proposals_count: 0,
+          elections_count: 0,
+          regions_count: 0,
+          maintainer_requirement_name: DEFAULT_MAINTAINER_REQUIREMENT_NAME.to_string(),
+          maintainer_requirement_age: DEFAULT_MAINTAINER_REQUIREMENT_AGE,
+          maintainer_claim_window: DEFAULT_MAINTAINER_CLAIM_WINDOW,
+          election_voting_period: DEFAULT_ELECTION_VOTING_PERIOD,
+          election_quorum: DEFAULT_ELECTION_QUORUM,
+          max_page_size: 2,
+          city_deletion_batch_size: DEFAULT_CITY_DELETION_BATCH_SIZE,
+          join_request_period: DEFAULT_JOIN_REQUEST_PERIOD,
+          protocol_fee_share: DEFAULT_PROTOCOL_FEE_SHARE,
+          guardian: None,
+        }
+      }
+    );
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCities { start_id: 0, limit: 10 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: vec![
+          CityResponse {
+            id: 0,
+            name: "Super City".to_string(),
+            power_level: 3,
+            population: 0,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None,
+          },
+          CityResponse {
+            id: 1,
+            name: "Secret City".to_string(),
+            power_level: 5,
+            population: 0,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None,
+          },
+        ]
+      }
+    );
+  }
+
+  #[test]
+  fn migration() {
+    let mut deps = mock_dependencies();
+
+    // Store config of contract version 1.0.0
+    let maintainer = deps.as_ref().api.addr_canonicalize("creator").unwrap();
+    Item::new("config_key")
+      .save(
+        deps.as_mut().storage,
+        &LegacyConfig {
+          maintainer,
+          cities_count: 0,
+        },
+      )
+      .unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(res.attributes[0].value, LEGACY_CONTRACT_VERSION.to_string());
+    assert_eq!(
+      get_contract_version(deps.as_ref().storage).unwrap().version,
+      CONTRACT_VERSION.to_string()
+    );
+
+    let config: ResponseMsg = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+      config,
+      ResponseMsg::Config {
+        config: ConfigResponse {
+          maintainer: Some(Addr::unchecked("creator")),
+          frozen: false,
+          cities_count: 0,
+          council_proposals_count: 0,
+          elections_count: 0,
+          regions_count: 0,
+          maintainer_requirement_name: DEFAULT_MAINTAINER_REQUIREMENT_NAME.to_string(),
+          maintainer_requirement_age: DEFAULT_MAINTAINER_REQUIREMENT_AGE,
+          maintainer_claim_window: DEFAULT_MAINTAINER_CLAIM_WINDOW,
+          election_voting_period: DEFAULT_ELECTION_VOTING_PERIOD,
+          election_quorum: DEFAULT_ELECTION_QUORUM,
+          max_page_size: DEFAULT_MAX_PAGE_SIZE,
+          city_deletion_batch_size: DEFAULT_CITY_DELETION_BATCH_SIZE,
+          join_request_period: DEFAULT_JOIN_REQUEST_PERIOD,
+          protocol_fee_share: DEFAULT_PROTOCOL_FEE_SHARE,
+          guardian: None,
+        }
+      }
+    );
+
+    // Migration to the same version keeps state
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+      Err(ContractError::CannotDowngrade { stored, current }) => {
+        assert_eq!(stored, "99.0.0".to_string());
+        assert_eq!(current, CONTRACT_VERSION.to_string());
+      }
+      _ => unreachable!(),
+    }
+
+    set_contract_version(deps.as_mut().storage, "crates.io:another_contract", "1.0.0").unwrap();
+    match migrate(deps.as_mut(), mock_env(), MigrateMsg {}) {
+      Err(ContractError::InvalidMigration { contract }) => {
+        assert_eq!(contract, "crates.io:another_contract".to_string())
+      }
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn maintainer_takeover() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.height = 887;
+    env.block.time = Timestamp::from_seconds(YEAR_IN_SECONDS * 52 + YEAR_IN_SECONDS / 2); // in middle of 2022
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2015 },
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_2", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2000 },
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_3", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2015 },
+        nickname: "Super_Maintainer_887".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_4", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2004 },
+        nickname: "Super_Maintainer_887".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+
+    let bad_takeover = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    );
+    assert!(bad_takeover.is_err());
+
+    let bad_takeover = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    );
+    assert!(bad_takeover.is_err());
+
+    let bad_takeover = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_2", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    );
+    assert!(bad_takeover.is_err());
+
+    let bad_takeover = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_3", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    );
+    assert!(bad_takeover.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_4", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    )
+    .unwrap();
+
+    let claim: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainerClaim {}).unwrap()).unwrap();
+    assert_eq!(
+      claim,
+      ResponseMsg::LookMaintainerClaim {
+        claim: Some(MaintainerClaimResponse {
+          claimant: Addr::unchecked("user_4"),
+          finalizable_at: Expiration::AtTime(env.block.time.plus_seconds(DAY_IN_SECONDS)),
+        })
+      }
+    );
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_4", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    ) {
+      Err(ContractError::MaintainerClaimPending {}) => {}
+      _ => unreachable!(),
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::FinalizeMaintainerClaim {},
+    ) {
+      Err(ContractError::ChallengeWindowNotOver {}) => {}
+      _ => unreachable!(),
+    }
+
+    let bad_veto = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::VetoMaintainerClaim {},
+    );
+    assert!(bad_veto.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::VetoMaintainerClaim {},
+    )
+    .unwrap();
+
+    let claim: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainerClaim {}).unwrap()).unwrap();
+    assert_eq!(claim, ResponseMsg::LookMaintainerClaim { claim: None });
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_4", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DAY_IN_SECONDS);
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::VetoMaintainerClaim {},
+    ) {
+      Err(ContractError::ChallengeWindowOver {}) => {}
+      _ => unreachable!(),
+    }
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("creator")),
+        frozen: false,
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::FinalizeMaintainerClaim {},
+    )
+    .unwrap();
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env, QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("user_4")),
+        frozen: false,
+      }
+    );
+  }
+
+  #[test]
+  fn config_updation() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(YEAR_IN_SECONDS * 52 + YEAR_IN_SECONDS / 2); // in middle of 2022
+
+    instantiate(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2010 },
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+
+    let bad_takeover = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    );
+    assert!(bad_takeover.is_err());
+
+    let bad_updation = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::UpdateConfig {
+        maintainer_requirement_name: Some("super_user".to_string()),
+        maintainer_requirement_age: Some(10),
+        election_voting_period: None,
+        election_quorum: None,
+        max_page_size: None,
+        city_deletion_batch_size: None,
+        join_request_period: None,
+        protocol_fee_share: None,
+      },
+    );
+    assert!(bad_updation.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateConfig {
+        maintainer_requirement_name: Some("super_user".to_string()),
+        maintainer_requirement_age: None,
+        election_voting_period: None,
+        election_quorum: Some(5),
+        max_page_size: None,
+        city_deletion_batch_size: None,
+        join_request_period: None,
+        protocol_fee_share: None,
+      },
+    )
+    .unwrap();
+
+    let config: ResponseMsg = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+      config,
+      ResponseMsg::Config {
+        config: ConfigResponse {
+          maintainer: Some(Addr::unchecked("creator")),
+          frozen: false,
+          cities_count: 0,
+          council_proposals_count: 0,
+          elections_count: 0,
+          regions_count: 0,
+          maintainer_requirement_name: "super_user".to_string(),
+          maintainer_requirement_age: 17,
+          maintainer_claim_window: DAY_IN_SECONDS,
+          election_voting_period: 3600,
+          election_quorum: 5,
+          max_page_size: 100,
+          city_deletion_batch_size: DEFAULT_CITY_DELETION_BATCH_SIZE,
+          join_request_period: DEFAULT_JOIN_REQUEST_PERIOD,
+          protocol_fee_share: DEFAULT_PROTOCOL_FEE_SHARE,
+          guardian: None,
+        }
+      }
+    );
+
+    let bad_takeover = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    );
+    assert!(bad_takeover.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateConfig {
+        maintainer_requirement_name: None,
+        maintainer_requirement_age: Some(10),
+        election_voting_period: None,
+        election_quorum: None,
+        max_page_size: None,
+        city_deletion_batch_size: None,
+        join_request_period: None,
+        protocol_fee_share: None,
+      },
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::BecomeMaintainer {},
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DAY_IN_SECONDS);
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::FinalizeMaintainerClaim {},
+    )
+    .unwrap();
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env, QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("user")),
+        frozen: false,
+      }
+    );
+  }
+
+  #[test]
+  fn maintainer_handover() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1000);
+
+    instantiate(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    let bad_proposal = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::ProposeMaintainer {
+        address: Addr::unchecked("user_1"),
+        expires_at: Expiration::AtTime(Timestamp::from_seconds(2000)),
+      },
+    );
+    assert!(bad_proposal.is_err());
+
+    let bad_proposal = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ProposeMaintainer {
+        address: Addr::unchecked("user_1"),
+        expires_at: Expiration::AtTime(Timestamp::from_seconds(500)),
+      },
+    );
+    assert!(bad_proposal.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ProposeMaintainer {
+        address: Addr::unchecked("user_1"),
+        expires_at: Expiration::AtTime(Timestamp::from_seconds(2000)),
+      },
+    )
+    .unwrap();
+
+    let proposal: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainerProposal {}).unwrap()).unwrap();
+    assert_eq!(
+      proposal,
+      ResponseMsg::LookMaintainerProposal {
+        proposal: Some(MaintainerProposalResponse {
+          candidate: Addr::unchecked("user_1"),
+          expires_at: Expiration::AtTime(Timestamp::from_seconds(2000)),
+        })
+      }
+    );
+
+    let bad_accept = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_2", &[]),
+      ExecuteMsg::AcceptMaintainer {},
+    );
+    assert!(bad_accept.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CancelMaintainerProposal {},
+    )
+    .unwrap();
+
+    let bad_accept = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_1", &[]),
+      ExecuteMsg::AcceptMaintainer {},
+    );
+    assert!(bad_accept.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ProposeMaintainer {
+        address: Addr::unchecked("user_2"),
+        expires_at: Expiration::AtTime(Timestamp::from_seconds(2000)),
+      },
+    )
+    .unwrap();
+
+    let mut late_env = env.clone();
+    late_env.block.time = Timestamp::from_seconds(3000);
+    let bad_accept = execute(
+      deps.as_mut(),
+      late_env,
+      mock_info("user_2", &[]),
+      ExecuteMsg::AcceptMaintainer {},
+    );
+    assert!(bad_accept.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user_2", &[]),
+      ExecuteMsg::AcceptMaintainer {},
+    )
+    .unwrap();
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("user_2")),
+        frozen: false,
+      }
+    );
+
+    let proposal: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env, QueryMsg::LookMaintainerProposal {}).unwrap()).unwrap();
+    assert_eq!(proposal, ResponseMsg::LookMaintainerProposal { proposal: None });
+  }
+
+  #[test]
+  fn council_mode() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    let bad_council = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCouncil {
+        members: vec![Addr::unchecked("member_1"), Addr::unchecked("member_2")],
+        threshold: 3,
+      },
+    );
+    assert!(bad_council.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCouncil {
+        members: vec![
+          Addr::unchecked("member_1"),
+          Addr::unchecked("member_2"),
+          Addr::unchecked("member_3"),
+        ],
+        threshold: 2,
+      },
+    )
+    .unwrap();
+
+    let council: ResponseMsg =
+      from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookCouncil {}).unwrap()).unwrap();
+    assert_eq!(
+      council,
+      ResponseMsg::LookCouncil {
+        council: Some(CouncilResponse {
+          members: vec![
+            Addr::unchecked("member_1"),
+            Addr::unchecked("member_2"),
+            Addr::unchecked("member_3"),
+          ],
+          threshold: 2,
+        })
+      }
+    );
+
+    let city_msg = ExecuteMsg::RegisterCity {
+      name: "Super City".to_string(),
+      power_level: 5,
+    };
+
+    let bad_city_creation = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), city_msg.clone());
+    assert!(bad_city_creation.is_err());
+
+    let bad_proposal = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CouncilPropose {
+        action: Box::new(city_msg.clone()),
+        expires_at: Expiration::Never {},
+      },
+    );
+    assert!(bad_proposal.is_err());
+
+    let bad_proposal = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_1", &[]),
+      ExecuteMsg::CouncilPropose {
+        action: Box::new(ExecuteMsg::BecomeMaintainer {}),
+        expires_at: Expiration::Never {},
+      },
+    );
+    assert!(bad_proposal.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_1", &[]),
+      ExecuteMsg::CouncilPropose {
+        action: Box::new(city_msg.clone()),
+        expires_at: Expiration::Never {},
+      },
+    )
+    .unwrap();
+
+    let bad_execution = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_1", &[]),
+      ExecuteMsg::CouncilExecute { proposal_id: 0 },
+    );
+    assert!(bad_execution.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_2", &[]),
+      ExecuteMsg::CouncilApprove { proposal_id: 0 },
+    )
+    .unwrap();
+
+    let bad_approval = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_2", &[]),
+      ExecuteMsg::CouncilApprove { proposal_id: 0 },
+    );
+    assert!(bad_approval.is_err());
+
+    let proposals: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCouncilProposals {
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      proposals,
+      ResponseMsg::LookCouncilProposals {
+        proposals: vec![CouncilProposalResponse {
+          id: 0,
+          proposer: Addr::unchecked("member_1"),
+          action: city_msg.clone(),
+          expires_at: Expiration::Never {},
+          approvals: vec![Addr::unchecked("member_1"), Addr::unchecked("member_2")],
+        }]
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_2", &[]),
+      ExecuteMsg::CouncilRevokeApproval { proposal_id: 0 },
+    )
+    .unwrap();
+
+    let bad_execution = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_1", &[]),
+      ExecuteMsg::CouncilExecute { proposal_id: 0 },
+    );
+    assert!(bad_execution.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_3", &[]),
+      ExecuteMsg::CouncilApprove { proposal_id: 0 },
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_3", &[]),
+      ExecuteMsg::CouncilExecute { proposal_id: 0 },
+    )
+    .unwrap();
+
+    let bad_execution = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_3", &[]),
+      ExecuteMsg::CouncilExecute { proposal_id: 0 },
+    );
+    assert!(bad_execution.is_err());
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCities { start_id: 0, limit: 10 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: vec![CityResponse {
+          id: 0,
+          name: "Super City".to_string(),
+          power_level: 5,
+          population: 0,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
+        }]
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_2", &[]),
+      ExecuteMsg::CouncilPropose {
+        action: Box::new(ExecuteMsg::SetCouncil {
+          members: vec![],
+          threshold: 0,
+        }),
+        expires_at: Expiration::Never {},
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_1", &[]),
+      ExecuteMsg::CouncilApprove { proposal_id: 1 },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("member_1", &[]),
+      ExecuteMsg::CouncilExecute { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let council: ResponseMsg =
+      from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookCouncil {}).unwrap()).unwrap();
+    assert_eq!(council, ResponseMsg::LookCouncil { council: None });
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Another City".to_string(),
+        power_level: 5,
+      },
+    )
+    .unwrap();
+  }
+
+  #[test]
+  fn roles_management() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    let city_msg = ExecuteMsg::RegisterCity {
+      name: "Super City".to_string(),
+      power_level: 5,
+    };
+
+    let bad_city_creation = execute(deps.as_mut(), mock_env(), mock_info("staff_1", &[]), city_msg.clone());
+    assert!(bad_city_creation.is_err());
+
+    let bad_granting = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("staff_1", &[]),
+      ExecuteMsg::GrantRole {
+        address: Addr::unchecked("staff_1"),
+        role: Role::CityCreator,
+      },
+    );
+    assert!(bad_granting.is_err());
+
+    for (address, role) in [
+      ("staff_1", Role::CityCreator),
+      ("staff_1", Role::Moderator),
+      ("staff_2", Role::CityCreator),
+      ("staff_3", Role::Moderator),
+    ] {
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        ExecuteMsg::GrantRole {
+          address: Addr::unchecked(address),
+          role,
+        },
+      )
+      .unwrap();
+    }
+
+    execute(deps.as_mut(), mock_env(), mock_info("staff_1", &[]), city_msg.clone()).unwrap();
+
+    let bad_city_creation = execute(deps.as_mut(), mock_env(), mock_info("staff_3", &[]), city_msg.clone());
+    assert!(bad_city_creation.is_err());
+
+    let roles: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookRoles {
+          address: Addr::unchecked("staff_1"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      roles,
+      ResponseMsg::LookRoles {
+        roles: vec![Role::CityCreator, Role::Moderator]
+      }
+    );
+
+    // Holders are ordered by canonical address
+    let holders = match from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookRoleHolders {
+          role: Role::CityCreator,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap()
+    {
+      ResponseMsg::LookRoleHolders { holders } => holders,
+      _ => unreachable!(),
+    };
+    assert_eq!(holders.len(), 2);
+    assert!(holders.contains(&Addr::unchecked("staff_1")));
+    assert!(holders.contains(&Addr::unchecked("staff_2")));
+
+    let next_holders: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookRoleHolders {
+          role: Role::CityCreator,
+          start_after: Some(holders[0].clone()),
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      next_holders,
+      ResponseMsg::LookRoleHolders {
+        holders: vec![holders[1].clone()]
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RevokeRole {
+        address: Addr::unchecked("staff_1"),
+        role: Role::CityCreator,
+      },
+    )
+    .unwrap();
+
+    let bad_revoking = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RevokeRole {
+        address: Addr::unchecked("staff_1"),
+        role: Role::CityCreator,
+      },
+    );
+    assert!(bad_revoking.is_err());
+
+    let bad_city_creation = execute(deps.as_mut(), mock_env(), mock_info("staff_1", &[]), city_msg);
+    assert!(bad_city_creation.is_err());
+
+    let roles: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookRoles {
+          address: Addr::unchecked("staff_1"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      roles,
+      ResponseMsg::LookRoles {
+        roles: vec![Role::Moderator]
+      }
+    );
+  }
+
+  #[test]
+  fn role_permissions() {
+    let mut deps = mock_dependencies();
+
+    let env = mock_env();
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    for (address, role) in [("moderator", Role::Moderator), ("city_creator", Role::CityCreator)] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        ExecuteMsg::GrantRole {
+          address: Addr::unchecked(address),
+          role,
+        },
+      )
+      .unwrap();
+    }
+    for user in ["user1", "user2"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+    }
+
+    // Moderator kicks, bans and unbans city members and archives cities, other roles do not
+    let moderation_msgs = [
+      ExecuteMsg::KickFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user1"),
+        reason: None,
+      },
+      ExecuteMsg::BanFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user2"),
+        until: Expiration::Never {},
+      },
+      ExecuteMsg::UnbanFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user2"),
+      },
+      ExecuteMsg::ArchiveCity { city_id: 0 },
+    ];
+    for msg in moderation_msgs {
+      match execute(deps.as_mut(), env.clone(), mock_info("city_creator", &[]), msg.clone()) {
+        Err(ContractError::MissingRole { role }) => assert_eq!(role, "moderator"),
+        _ => unreachable!(),
+      }
+      execute(deps.as_mut(), env.clone(), mock_info("moderator", &[]), msg).unwrap();
+    }
+
+    // Roles do not grant withdrawal of protocol fees
+    match execute(
+      deps.as_mut(),
+      env,
+      mock_info("moderator", &[]),
+      ExecuteMsg::WithdrawProtocolFees {
+        denom: Denom::Native("uatom".to_string()),
+        amount: None,
+        recipient: None,
+      },
+    ) {
+      Err(ContractError::Unauthorized {}) => {}
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn pause_switch() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Super City".to_string(),
+        power_level: 3,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2000 },
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+
+    let bad_pause = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("guardian", &[]),
+      ExecuteMsg::Pause {
+        action: Some("register_in_city".to_string()),
+        reason: "frontend bug".to_string(),
+      },
+    );
+    assert!(bad_pause.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetGuardian {
+        guardian: Some(Addr::unchecked("guardian")),
+      },
+    )
+    .unwrap();
+
+    // Unknown and not pausable actions are rejected
+    for action in ["register_in_cty", "unpause"] {
+      match execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian", &[]),
+        ExecuteMsg::Pause {
+          action: Some(action.to_string()),
+          reason: "frontend bug".to_string(),
+        },
+      ) {
+        Err(ContractError::InconsistentData {}) => {}
+        _ => unreachable!(),
+      }
+    }
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("guardian", &[]),
+      ExecuteMsg::Pause {
+        action: Some("register_in_city".to_string()),
+        reason: "frontend bug".to_string(),
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::Paused { action, reason }) => {
+        assert_eq!(action, "register_in_city".to_string());
+        assert_eq!(reason, "frontend bug".to_string());
+      }
+      _ => unreachable!(),
+    }
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user", &[]),
+      ExecuteMsg::UpdatePerson {
+        nickname: "super_puper_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::Pause {
+        action: None,
+        reason: "maintenance".to_string(),
+      },
+    )
+    .unwrap();
+
+    let pauses: ResponseMsg = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookPauses {}).unwrap()).unwrap();
+    assert_eq!(
+      pauses,
+      ResponseMsg::LookPauses {
+        reason: Some("maintenance".to_string()),
+        actions: vec![PausedAction {
+          action: "register_in_city".to_string(),
+          reason: "frontend bug".to_string(),
+        }]
+      }
+    );
+
+    let bad_updation = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user", &[]),
+      ExecuteMsg::UpdatePerson {
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    );
+    assert!(bad_updation.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("guardian", &[]),
+      ExecuteMsg::Unpause { action: None },
+    )
+    .unwrap();
+
+    let bad_unpause = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("guardian", &[]),
+      ExecuteMsg::Unpause { action: None },
+    );
+    assert!(bad_unpause.is_err());
+
+    let bad_registring_in_city = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    );
+    assert!(bad_registring_in_city.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::Unpause {
+        action: Some("register_in_city".to_string()),
+      },
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    let pauses: ResponseMsg = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookPauses {}).unwrap()).unwrap();
+    assert_eq!(
+      pauses,
+      ResponseMsg::LookPauses {
+        reason: None,
+        actions: vec![]
+      }
+    );
+  }
+
+  #[test]
+  fn maintainer_election() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    instantiate(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+    for name in ["Super City", "Secret City"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        ExecuteMsg::RegisterCity {
+          name: name.to_string(),
+          power_level: 3,
+        },
+      )
+      .unwrap();
+    }
+    for user in ["alice", "bob", "carol"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 2000 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+    for (user, city_id) in [("alice", 0), ("alice", 1), ("bob", 0)] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+    }
+
+    let bad_nomination = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("dave", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked("bob"),
+      },
+    );
+    assert!(bad_nomination.is_err());
+
+    let nomination = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked("bob"),
+      },
+    )
+    .unwrap();
+    assert_eq!(nomination.attributes[0].value, "0".to_string());
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("alice", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked("bob"),
+      },
+    ) {
+      Err(ContractError::AlreadyNominated {}) => {}
+      _ => unreachable!(),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("alice", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked("alice"),
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::Vote {
+        candidate: Addr::unchecked("bob"),
+      },
+    ) {
+      Err(ContractError::NoVotingPower {}) => {}
+      _ => unreachable!(),
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("bob", &[]),
+      ExecuteMsg::Vote {
+        candidate: Addr::unchecked("carol"),
+      },
+    ) {
+      Err(ContractError::NotNominated {}) => {}
+      _ => unreachable!(),
+    }
+
+    for (user, candidate) in [("bob", "bob"), ("alice", "bob"), ("alice", "alice")] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::Vote {
+          candidate: Addr::unchecked(candidate),
+        },
+      )
+      .unwrap();
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::TallyElection {},
+    ) {
+      Err(ContractError::VotingNotOver {}) => {}
+      _ => unreachable!(),
+    }
+
+    env.block.time = Timestamp::from_seconds(1_650_000_000 + 3600);
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("bob", &[]),
+      ExecuteMsg::Vote {
+        candidate: Addr::unchecked("bob"),
+      },
+    ) {
+      Err(ContractError::VotingClosed {}) => {}
+      _ => unreachable!(),
+    }
+
+    let bad_finalization = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::FinalizeElection {},
+    );
+    assert!(bad_finalization.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::TallyElection {},
+    )
+    .unwrap();
+
+    let election: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookElection {}).unwrap()).unwrap();
+    assert_eq!(
+      election,
+      ResponseMsg::LookElection {
+        election: Some(ElectionResponse {
+          id: 0,
+          status: ElectionStatus::Tallied,
+          ends_at: Expiration::AtTime(Timestamp::from_seconds(1_650_000_000 + 3600)),
+          quorum: 2,
+          total_weight: 3,
+          winner: Some(Addr::unchecked("alice")),
+        })
+      }
+    );
+
+    match from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookElectionResults {
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap()
+    {
+      ResponseMsg::LookElectionResults { candidates } => {
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates.contains(&CandidateResponse {
+          candidate: Addr::unchecked("alice"),
+          votes: 2,
+        }));
+        assert!(candidates.contains(&CandidateResponse {
+          candidate: Addr::unchecked("bob"),
+          votes: 1,
+        }));
+      }
+      _ => unreachable!(),
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::TallyElection {},
+    ) {
+      Err(ContractError::ElectionAlreadyTallied {}) => {}
+      _ => unreachable!(),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::FinalizeElection {},
+    )
+    .unwrap();
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("alice")),
+        frozen: false,
+      }
+    );
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("carol", &[]),
+      ExecuteMsg::FinalizeElection {},
+    ) {
+      Err(ContractError::ElectionNotTallied {}) => {}
+      _ => unreachable!(),
+    }
+
+    let nomination = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("bob", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked("bob"),
+      },
+    )
+    .unwrap();
+    assert_eq!(nomination.attributes[0].value, "1".to_string());
+
+    // Quorum is not reached, maintainer is not changed
+    env.block.time = Timestamp::from_seconds(1_650_000_000 + 2 * 3600);
+    for msg in [ExecuteMsg::TallyElection {}, ExecuteMsg::FinalizeElection {}] {
+      execute(deps.as_mut(), env.clone(), mock_info("carol", &[]), msg).unwrap();
+    }
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env, QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: Some(Addr::unchecked("alice")),
+        frozen: false,
+      }
+    );
+  }
+
+  #[test]
+  fn election_candidates_limit() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    instantiate(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+    for i in 0..=MAX_ELECTION_CANDIDATES {
+      let user = format!("user{}", i);
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(&user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 2000 },
+          nickname: user.clone(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+
+    for i in 0..MAX_ELECTION_CANDIDATES {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("user0", &[]),
+        ExecuteMsg::Nominate {
+          candidate: Addr::unchecked(format!("user{}", i)),
+        },
+      )
+      .unwrap();
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user0", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked(format!("user{}", MAX_ELECTION_CANDIDATES)),
+      },
+    ) {
+      Err(ContractError::TooManyCandidates { max }) => assert_eq!(max, MAX_ELECTION_CANDIDATES),
+      _ => unreachable!(),
+    }
+
+    // Already nominated candidate is still reported as such
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user0", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked("user1"),
+      },
+    ) {
+      Err(ContractError::AlreadyNominated {}) => {}
+      _ => unreachable!(),
+    }
+
+    // Next election starts with no candidates
+    env.block.time = env
+      .block
+      .time
+      .plus_seconds(get_storage(&deps.storage).unwrap().election_voting_period + 1);
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user0", &[]),
+      ExecuteMsg::TallyElection {},
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user0", &[]),
+      ExecuteMsg::FinalizeElection {},
+    )
+    .unwrap();
+    let nomination = execute(
+      deps.as_mut(),
+      env,
+      mock_info("user0", &[]),
+      ExecuteMsg::Nominate {
+        candidate: Addr::unchecked(format!("user{}", MAX_ELECTION_CANDIDATES)),
+      },
+    )
+    .unwrap();
+    assert_eq!(nomination.attributes[0].value, "1".to_string());
+  }
+
+  #[test]
+  fn maintainer_renouncement() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(YEAR_IN_SECONDS * 52 + YEAR_IN_SECONDS / 2); // in middle of 2022
+
+    instantiate(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::GrantRole {
+        address: Addr::unchecked("city_creator"),
+        role: Role::CityCreator,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetGuardian {
+        guardian: Some(Addr::unchecked("guardian")),
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("guardian", &[]),
+      ExecuteMsg::Pause {
+        action: Some("register_in_city".to_string()),
+        reason: "frontend bug".to_string(),
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 2000 },
+        nickname: "Super_Maintainer_887".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+
+    let bad_renouncement = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RenounceMaintainer {},
+    );
+    assert!(bad_renouncement.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RenounceMaintainer {},
+    )
+    .unwrap();
+
+    let maintainer: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
+    assert_eq!(
+      maintainer,
+      ResponseMsg::LookMaintainer {
+        maintainer: None,
+        frozen: true,
+      }
+    );
+
+    let pauses: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookPauses {}).unwrap()).unwrap();
+    assert_eq!(
+      pauses,
+      ResponseMsg::LookPauses {
+        reason: None,
+        actions: vec![]
+      }
+    );
+
+    for (sender, msg) in [
+      (
+        "creator",
+        ExecuteMsg::RegisterCity {
+          name: "Super City".to_string(),
+          power_level: 3,
+        },
+      ),
+      (
+        "city_creator",
+        ExecuteMsg::RegisterCity {
+          name: "Super City".to_string(),
+          power_level: 3,
+        },
+      ),
+      (
+        "guardian",
+        ExecuteMsg::Pause {
+          action: None,
+          reason: "maintenance".to_string(),
+        },
+      ),
+      ("creator", ExecuteMsg::RenounceMaintainer {}),
+      ("user", ExecuteMsg::BecomeMaintainer {}),
+      (
+        "user",
+        ExecuteMsg::Nominate {
+          candidate: Addr::unchecked("user"),
+        },
+      ),
+    ] {
+      match execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg) {
+        Err(ContractError::Frozen {}) => {}
+        _ => unreachable!(),
+      }
+    }
+  }
+
+  #[test]
+  fn city_creation() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Super City".to_string(),
+        power_level: 5,
+      },
+    )
+    .unwrap();
+
+    let bad_city_creation = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("mad", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Mad City".to_string(),
+        power_level: 7,
+      },
+    );
+    assert!(bad_city_creation.is_err());
+
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Secret City".to_string(),
+        power_level: 3,
+      },
+    )
+    .unwrap();
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCities { start_id: 0, limit: 10 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: [
+          CityResponse {
+            id: 0,
+            name: "Super City".to_string(),
+            power_level: 5,
+            population: 0,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
+          },
+          CityResponse {
+            id: 1,
+            name: "Secret City".to_string(),
+            power_level: 3,
+            population: 0,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
+          },
+        ]
+        .to_vec()
+      }
+    );
+  }
+
+  #[test]
+  fn city_updation() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1000);
+
+    instantiate(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Supr City".to_string(),
+        power_level: 5,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 1970 },
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("admin", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "Super City".to_string(),
+        power_level: 6,
+      },
+    ) {
+      Err(ContractError::NotCityAdmin {}) => {}
+      _ => unreachable!(),
+    }
+
+    let bad_mayor_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("admin", &[]),
+      ExecuteMsg::SetMayor {
+        city_id: 0,
+        mayor: Some(Addr::unchecked("admin")),
+      },
+    );
+    assert!(bad_mayor_setting.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetMayor {
+        city_id: 0,
+        mayor: Some(Addr::unchecked("admin")),
+      },
+    )
+    .unwrap();
+
+    let bad_updation = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("admin", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "  ".to_string(),
+        power_level: 6,
+      },
+    );
+    assert!(bad_updation.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("admin", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "Super City".to_string(),
+        power_level: 6,
+      },
+    )
+    .unwrap();
+
+    env.block.time = Timestamp::from_seconds(2000);
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "Super Duper City".to_string(),
+        power_level: 6,
+      },
+    )
+    .unwrap();
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCities { start_id: 0, limit: 10 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: vec![CityResponse {
+          id: 0,
+          name: "Super Duper City".to_string(),
+          power_level: 6,
+          population: 1,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
+        }]
+      }
+    );
+
+    let history: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env,
+        QueryMsg::LookCityHistory {
+          city_id: 0,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      history,
+      ResponseMsg::LookCityHistory {
+        changes: vec![
+          CityChangeResponse {
+            id: 0,
+            changed_by: Addr::unchecked("admin"),
+            changed_at: Timestamp::from_seconds(1000),
+            previous_name: "Supr City".to_string(),
+            previous_power_level: 5,
+            name: "Super City".to_string(),
+            power_level: 6,
+          },
+          CityChangeResponse {
+            id: 1,
+            changed_by: Addr::unchecked("creator"),
+            changed_at: Timestamp::from_seconds(2000),
+            previous_name: "Super City".to_string(),
+            previous_power_level: 6,
+            name: "Super Duper City".to_string(),
+            power_level: 6,
+          },
+        ]
+      }
+    );
+  }
+
+  #[test]
+  fn city_mayorship() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![
+      CitySeed {
+        name: "Super City".to_string(),
+        power_level: 3,
+      },
+      CitySeed {
+        name: "Secret City".to_string(),
+        power_level: 5,
+      },
+    ]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let bad_mayor_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("mayor", &[]),
+      ExecuteMsg::SetMayor {
+        city_id: 0,
+        mayor: Some(Addr::unchecked("mayor")),
+      },
+    );
+    assert!(bad_mayor_setting.is_err());
+
+    for city_id in [0, 1] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        ExecuteMsg::SetMayor {
+          city_id,
+          mayor: Some(Addr::unchecked("mayor")),
+        },
+      )
+      .unwrap();
+    }
+
+    let mayor: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookMayor { city_id: 0 }).unwrap()).unwrap();
+    assert_eq!(
+      mayor,
+      ResponseMsg::LookMayor {
+        mayor: Some(Addr::unchecked("mayor"))
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("mayor", &[]),
+      ExecuteMsg::AppointDeputy {
+        city_id: 0,
+        deputy: Addr::unchecked("deputy"),
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("mayor", &[]),
+      ExecuteMsg::AppointDeputy {
+        city_id: 0,
+        deputy: Addr::unchecked("deputy"),
+      },
+    ) {
+      Err(ContractError::AlreadyCityOfficial {}) => {}
+      _ => unreachable!(),
+    }
+
+    // Deputy is not able to appoint deputies
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("deputy", &[]),
+      ExecuteMsg::AppointDeputy {
+        city_id: 0,
+        deputy: Addr::unchecked("user"),
+      },
+    ) {
+      Err(ContractError::NotMayor {}) => {}
+      _ => unreachable!(),
+    }
+
+    let deputies: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCityDeputies {
+          city_id: 0,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      deputies,
+      ResponseMsg::LookCityDeputies {
+        deputies: vec![Addr::unchecked("deputy")]
+      }
+    );
+
+    let administered_cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookAdministeredCities {
+          administrator: Addr::unchecked("mayor"),
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      administered_cities,
+      ResponseMsg::LookAdministeredCities {
+        cities: vec![
+          AdministeredCityResponse {
+            city_id: 0,
+            office: CityOffice::Mayor,
+          },
+          AdministeredCityResponse {
+            city_id: 1,
+            office: CityOffice::Mayor,
+          },
+        ]
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 1970 },
+        nickname: "super_user".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    let bad_kick = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user", &[]),
+      ExecuteMsg::KickFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user"),
+        reason: None,
+      },
+    );
+    assert!(bad_kick.is_err());
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("deputy", &[]),
+      ExecuteMsg::KickFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user"),
+        reason: Some("Spam".to_string()),
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "Spam".to_string());
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCities { start_id: 0, limit: 1 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match cities {
+      ResponseMsg::LookCities { cities } => assert_eq!(cities[0].population, 0),
+      _ => unreachable!(),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("mayor", &[]),
+      ExecuteMsg::DismissDeputy {
+        city_id: 0,
+        deputy: Addr::unchecked("deputy"),
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("deputy", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "Deputy City".to_string(),
+        power_level: 3,
+      },
+    ) {
+      Err(ContractError::NotCityAdmin {}) => {}
+      _ => unreachable!(),
+    }
+
+    // Removed mayor loses the office
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetMayor {
+        city_id: 1,
+        mayor: None,
+      },
+    )
+    .unwrap();
+
+    let administered_cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env,
+        QueryMsg::LookAdministeredCities {
+          administrator: Addr::unchecked("mayor"),
+          start_after: Some(0),
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      administered_cities,
+      ResponseMsg::LookAdministeredCities { cities: vec![] }
+    );
+  }
+
+  #[test]
+  fn city_join_policies() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.join_request_period = Some(DAY_IN_SECONDS);
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    for user in ["user1", "user2", "user3"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+
+    let bad_policy_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::SetJoinPolicy {
+        city_id: 0,
+        join_policy: JoinPolicy::ApprovalRequired,
+      },
+    );
+    assert!(bad_policy_setting.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetJoinPolicy {
+        city_id: 0,
+        join_policy: JoinPolicy::ApprovalRequired,
+      },
+    )
+    .unwrap();
+
+    for user in ["user1", "user2"] {
+      let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+      assert_eq!(res.attributes[0].value, "requested".to_string());
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::JoinRequestPending {}) => {}
+      _ => unreachable!(),
+    }
+
+    let requests: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookPersonJoinRequests {
+          person: Addr::unchecked("user1"),
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      requests,
+      ResponseMsg::LookPersonJoinRequests {
+        requests: vec![JoinRequestResponse {
+          city_id: 0,
+          person: Addr::unchecked("user1"),
+          expires_at: Expiration::AtTime(env.block.time.plus_seconds(DAY_IN_SECONDS)),
+        }]
+      }
+    );
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ApproveJoinRequest {
+        city_id: 0,
+        person: Addr::unchecked("user1"),
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RejectJoinRequest {
+        city_id: 0,
+        person: Addr::unchecked("user2"),
+      },
+    )
+    .unwrap();
+
+    let requests: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCityJoinRequests {
+          city_id: 0,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(requests, ResponseMsg::LookCityJoinRequests { requests: vec![] });
+
+    // Expired join request is not able to be approved
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+    env.block.time = env.block.time.plus_seconds(DAY_IN_SECONDS);
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ApproveJoinRequest {
+        city_id: 0,
+        person: Addr::unchecked("user2"),
+      },
+    ) {
+      Err(ContractError::JoinRequestExpired {}) => {}
+      _ => unreachable!(),
+    }
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::CancelJoinRequest { city_id: 0 },
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetJoinPolicy {
+        city_id: 0,
+        join_policy: JoinPolicy::InviteOnly,
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user3", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::NotInvited { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::InviteToCity {
+        city_id: 0,
+        person: Addr::unchecked("user3"),
+      },
+    )
+    .unwrap();
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user3", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "registered".to_string());
+
+    let cities: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env, QueryMsg::LookCities { start_id: 0, limit: 1 }).unwrap()).unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: vec![CityResponse {
+          id: 0,
+          name: "Super City".to_string(),
+          power_level: 3,
+          population: 2,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::InviteOnly,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None,
+        }]
+      }
+    );
+  }
+
+  #[test]
+  fn city_invite_codes() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetJoinPolicy {
+        city_id: 0,
+        join_policy: JoinPolicy::InviteOnly,
+      },
+    )
+    .unwrap();
+
+    for user in ["user1", "user2", "user3"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+
+    let code_hash = hex::encode(Sha256::digest(b"secret code"));
+    let expires_at = Expiration::AtTime(env.block.time.plus_seconds(DAY_IN_SECONDS));
+
+    let bad_code_creation = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CreateInviteCode {
+        city_id: 0,
+        code_hash: "secret code".to_string(),
+        expires_at,
+        uses: 2,
+      },
+    );
+    assert!(bad_code_creation.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CreateInviteCode {
+        city_id: 0,
+        code_hash: code_hash.clone(),
+        expires_at,
+        uses: 2,
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: Some("wrong code".to_string()),
+      },
+    ) {
+      Err(ContractError::InvalidInviteCode {}) => {}
+      _ => unreachable!(),
+    }
+
+    for user in ["user1", "user2"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: Some("secret code".to_string()),
+        },
+      )
+      .unwrap();
+    }
+
+    // Used up code is removed
+    let invite_code: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookInviteCode {
+          city_id: 0,
+          code_hash: code_hash.clone(),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(invite_code, ResponseMsg::LookInviteCode { invite_code: None });
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CreateInviteCode {
+        city_id: 0,
+        code_hash: code_hash.clone(),
+        expires_at,
+        uses: 5,
+      },
+    )
+    .unwrap();
+
+    let invite_code: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookInviteCode {
+          city_id: 0,
+          code_hash: code_hash.clone(),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      invite_code,
+      ResponseMsg::LookInviteCode {
+        invite_code: Some(InviteCodeResponse {
+          city_id: 0,
+          code_hash,
+          expires_at,
+          remaining_uses: 5,
+        })
+      }
+    );
+
+    env.block.time = env.block.time.plus_seconds(DAY_IN_SECONDS);
+    match execute(
+      deps.as_mut(),
+      env,
+      mock_info("user3", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: Some("secret code".to_string()),
+      },
+    ) {
+      Err(ContractError::InviteCodeExpired {}) => {}
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn city_waitlist() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let bad_capacity_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 0,
+        max_members: Some(0),
+      },
+    );
+    assert!(bad_capacity_setting.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 0,
+        max_members: Some(1),
+      },
+    )
+    .unwrap();
+
+    for (user, status) in [
+      ("user1", "registered"),
+      ("user2", "waitlisted"),
+      ("user3", "waitlisted"),
+      ("user4", "waitlisted"),
+    ] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+      let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+      assert_eq!(res.attributes[0].value, status.to_string());
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::AlreadyWaitlisted {}) => {}
+      _ => unreachable!(),
+    }
+
+    let position: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookWaitlistPosition {
+          city_id: 0,
+          person: Addr::unchecked("user3"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      position,
+      ResponseMsg::LookWaitlistPosition {
+        position: Some(1),
+        length: 3,
+      }
+    );
+
+    // Head of waitlist is admitted to the free slot
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::UnregisterFromCity { city_id: 0 },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user3", &[]),
+      ExecuteMsg::LeaveWaitlist { city_id: 0 },
+    )
+    .unwrap();
+
+    let position: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookWaitlistPosition {
+          city_id: 0,
+          person: Addr::unchecked("user4"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      position,
+      ResponseMsg::LookWaitlistPosition {
+        position: Some(0),
+        length: 1,
+      }
+    );
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 0,
+        max_members: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "1".to_string());
+
+    let people: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env,
+        QueryMsg::LookCityPeople {
+          city: 0,
+          start_id: 0,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match people {
+      ResponseMsg::LookCityPeople { people } => assert_eq!(
+        people
+          .iter()
+          .map(|person| person.address.clone())
+          .collect::<Vec<Addr>>(),
+        vec![Addr::unchecked("user2"), Addr::unchecked("user4")]
+      ),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn city_age_limits() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(YEAR_IN_SECONDS * 52 + YEAR_IN_SECONDS / 2); // in middle of 2022
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let bad_limits_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityAgeLimits {
+        city_id: 0,
+        min_age: Some(60),
+        max_age: Some(18),
+      },
+    );
+    assert!(bad_limits_setting.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityAgeLimits {
+        city_id: 0,
+        min_age: Some(18),
+        max_age: Some(60),
+      },
+    )
+    .unwrap();
+
+    for (user, year) in [("user1", 1970), ("user2", 1990), ("user3", 1980), ("user4", 2010)] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+    for user in ["user1", "user2", "user3"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::AgeRestricted { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    // Members over new maximal age are evicted by sweep
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityAgeLimits {
+        city_id: 0,
+        min_age: Some(18),
+        max_age: Some(40),
+      },
+    )
+    .unwrap();
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::EvictOverage {
+        city_id: 0,
+        start_id: 0,
+        limit: 2,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "2".to_string());
+    assert_eq!(res.attributes[1].value, "0".to_string());
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::EvictOverage {
+        city_id: 0,
+        start_id: 0,
+        limit: 10,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "0".to_string());
+    assert_eq!(res.attributes[1].value, "1".to_string());
+
+    let people: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env,
+        QueryMsg::LookCityPeople {
+          city: 0,
+          start_id: 0,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match people {
+      ResponseMsg::LookCityPeople { people } => assert_eq!(
+        people
+          .iter()
+          .map(|person| person.address.clone())
+          .collect::<Vec<Addr>>(),
+        vec![Addr::unchecked("user2")]
+      ),
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn city_deletion() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.city_deletion_batch_size = Some(2);
+    msg.seed_cities = Some(vec![
+      CitySeed {
+        name: "Super City".to_string(),
+        power_level: 3,
+      },
+      CitySeed {
+        name: "Secret City".to_string(),
+        power_level: 5,
+      },
+    ]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    for user in ["user1", "user2", "user3"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+    }
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 1,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::DeleteCity { city_id: 0 },
+    ) {
+      Err(ContractError::CityNotArchived { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    let bad_archivation = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::ArchiveCity { city_id: 0 },
+    );
+    assert!(bad_archivation.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ArchiveCity { city_id: 0 },
+    )
+    .unwrap();
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 1970 },
+        nickname: "user4".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::CityArchived { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    let bad_deletion = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::DeleteCity { city_id: 0 },
+    );
+    assert!(bad_deletion.is_err());
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::DeleteCity { city_id: 0 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "1".to_string());
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCities { start_id: 0, limit: 10 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: vec![
+          CityResponse {
+            id: 0,
+            name: "Super City".to_string(),
+            power_level: 3,
+            population: 1,
+            status: CityStatus::Deleting,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None,
+          },
+          CityResponse {
+            id: 1,
+            name: "Secret City".to_string(),
+            power_level: 5,
+            population: 1,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None,
+          },
+        ]
+      }
+    );
+
+    // Deletion is continued by anyone
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::DeleteCity { city_id: 0 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "0".to_string());
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCities { start_id: 0, limit: 1 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      cities,
+      ResponseMsg::LookCities {
+        cities: vec![CityResponse {
+          id: 1,
+          name: "Secret City".to_string(),
+          power_level: 5,
+          population: 1,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None,
+        }]
+      }
+    );
+
+    let person: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookPerson {
+          person: Addr::unchecked("user1"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match person {
+      ResponseMsg::LookPerson { person } => assert_eq!(person.resident_times, 1),
+      _ => unreachable!(),
+    }
+
+    let deleted_city_deletion = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::DeleteCity { city_id: 0 },
+    );
+    assert!(deleted_city_deletion.is_err());
+
+    // Join requests, invites and bans are removed with the city, kept join fees are refunded
+    for msg in [
+      ExecuteMsg::SetJoinFee {
+        city_id: 1,
+        join_fee: Some(coin(100, "uatom")),
+      },
+      ExecuteMsg::SetJoinPolicy {
+        city_id: 1,
+        join_policy: JoinPolicy::ApprovalRequired,
+      },
+      ExecuteMsg::BanFromCity {
+        city_id: 1,
+        person: Addr::unchecked("user2"),
+        until: Expiration::Never {},
+      },
+      ExecuteMsg::InviteToCity {
+        city_id: 1,
+        person: Addr::unchecked("user3"),
+      },
+    ] {
+      execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+    }
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &coins(100, "uatom")),
+      ExecuteMsg::RegisterInCity {
+        city_id: 1,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ArchiveCity { city_id: 1 },
+    )
+    .unwrap();
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::DeleteCity { city_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "1".to_string());
+    assert_eq!(res.attributes[1].value, "false".to_string());
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user4", &[]),
+      ExecuteMsg::DeleteCity { city_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "0".to_string());
+    assert_eq!(res.attributes[1].value, "true".to_string());
+
+    let requests: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookPersonJoinRequests {
+          person: Addr::unchecked("user4"),
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(requests, ResponseMsg::LookPersonJoinRequests { requests: vec![] });
+
+    let refunds: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookRefunds {
+          person: Addr::unchecked("user4"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      refunds,
+      ResponseMsg::LookRefunds {
+        refunds: coins(100, "uatom"),
+        cw20_refunds: vec![],
+      }
+    );
+
+    let ban_status: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env,
+        QueryMsg::LookBanStatus {
+          city_id: 1,
+          person: Addr::unchecked("user2"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      ban_status,
+      ResponseMsg::LookBanStatus {
+        banned: false,
+        until: None,
+      }
+    );
+  }
+
+  #[test]
+  fn city_join_fees() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.protocol_fee_share = Some(1000);
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let bad_fee_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetJoinFee {
+        city_id: 0,
+        join_fee: Some(coin(0, "uatom")),
+      },
+    );
+    assert!(bad_fee_setting.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetJoinFee {
+        city_id: 0,
+        join_fee: Some(coin(100, "uatom")),
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 0,
+        max_members: Some(1),
+      },
+    )
+    .unwrap();
+
+    for user in ["user1", "user2"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::Payment(_)) => {}
+      _ => unreachable!(),
+    }
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &coins(50, "uatom")),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::InsufficientFee { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    // Overpayment is refunded
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &coins(150, "uatom")),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "registered".to_string());
+    assert_eq!(
+      res.messages[0].msg,
+      CosmosMsg::Bank(BankMsg::Send {
+        to_address: "user1".to_string(),
+        amount: coins(50, "uatom"),
+      })
+    );
+
+    // Join fee of waiting person is kept until the person is registered
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &coins(100, "uatom")),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "waitlisted".to_string());
+
+    let escrow: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookEscrowedJoinFee {
+          city_id: 0,
+          person: Addr::unchecked("user2"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      escrow,
+      ResponseMsg::LookEscrowedJoinFee {
+        join_fee: Some(coin(100, "uatom")),
+        cw20_join_fee: None,
+      }
+    );
+
+    let treasury: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookCityTreasury { city_id: 0 }).unwrap()).unwrap();
+    assert_eq!(
+      treasury,
+      ResponseMsg::LookCityTreasury {
+        balances: coins(90, "uatom"),
+        cw20_balances: vec![],
+      }
+    );
+    let treasury: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookProtocolTreasury {}).unwrap()).unwrap();
+    assert_eq!(
+      treasury,
+      ResponseMsg::LookProtocolTreasury {
+        balances: coins(10, "uatom"),
+        cw20_balances: vec![],
+      }
+    );
+
+    // Join fee is refunded once the person leaves waitlist
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::LeaveWaitlist { city_id: 0 },
+    )
+    .unwrap();
+
+    let refunds: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookRefunds {
+          person: Addr::unchecked("user2"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      refunds,
+      ResponseMsg::LookRefunds {
+        refunds: coins(100, "uatom"),
+        cw20_refunds: vec![],
+      }
+    );
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::ClaimRefund {
+        denom: Denom::Native("uatom".to_string()),
+      },
+    )
+    .unwrap();
+    assert_eq!(
+      res.messages[0].msg,
+      CosmosMsg::Bank(BankMsg::Send {
+        to_address: "user2".to_string(),
+        amount: coins(100, "uatom"),
+      })
+    );
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::ClaimRefund {
+        denom: Denom::Native("uatom".to_string()),
+      },
+    ) {
+      Err(ContractError::InsufficientBalance { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    // Treasury is withdrawn by city admin or maintainer
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::WithdrawCityTreasury {
+        city_id: 0,
+        denom: Denom::Native("uatom".to_string()),
+        amount: None,
+        recipient: None,
+      },
+    ) {
+      Err(ContractError::NotCityAdmin {}) => {}
+      _ => unreachable!(),
+    }
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::WithdrawCityTreasury {
+        city_id: 0,
+        denom: Denom::Native("uatom".to_string()),
+        amount: Some(Uint128::new(100)),
+        recipient: None,
+      },
+    ) {
+      Err(ContractError::InsufficientBalance { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::WithdrawCityTreasury {
+        city_id: 0,
+        denom: Denom::Native("uatom".to_string()),
+        amount: None,
+        recipient: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(
+      res.messages[0].msg,
+      CosmosMsg::Bank(BankMsg::Send {
+        to_address: "creator".to_string(),
+        amount: coins(90, "uatom"),
+      })
+    );
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::WithdrawProtocolFees {
+        denom: Denom::Native("uatom".to_string()),
+        amount: Some(Uint128::new(4)),
+        recipient: Some(Addr::unchecked("treasurer")),
+      },
+    )
+    .unwrap();
+    assert_eq!(
+      res.messages[0].msg,
+      CosmosMsg::Bank(BankMsg::Send {
+        to_address: "treasurer".to_string(),
+        amount: coins(4, "uatom"),
+      })
+    );
+
+    let treasury: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env, QueryMsg::LookProtocolTreasury {}).unwrap()).unwrap();
+    assert_eq!(
+      treasury,
+      ResponseMsg::LookProtocolTreasury {
+        balances: coins(6, "uatom"),
+        cw20_balances: vec![],
+      }
+    );
+  }
+
+  #[test]
+  fn city_cw20_join_fees() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let bad_fee_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCw20JoinFee {
+        city_id: 0,
+        token: Addr::unchecked("token"),
+        amount: Some(Uint128::zero()),
+      },
+    );
+    assert!(bad_fee_setting.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCw20JoinFee {
+        city_id: 0,
+        token: Addr::unchecked("token"),
+        amount: Some(Uint128::new(100)),
+      },
+    )
+    .unwrap();
+
+    let join_fees: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookCw20JoinFees { city_id: 0 }).unwrap()).unwrap();
+    assert_eq!(
+      join_fees,
+      ResponseMsg::LookCw20JoinFees {
+        join_fees: vec![Cw20Coin {
+          address: "token".to_string(),
+          amount: Uint128::new(100),
+        }],
+      }
+    );
+
+    // City accepting cw20 tokens only is not joined for free
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 1970 },
+        nickname: "user1".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::NativeFeeNotAccepted {}) => {}
+      _ => unreachable!(),
+    }
+
+    let join_msg = to_binary(&ReceiveMsg::JoinCity {
+      city_id: 0,
+      invite_code: None,
+    })
+    .unwrap();
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("other_token", &[]),
+      ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "user1".to_string(),
+        amount: Uint128::new(100),
+        msg: join_msg.clone(),
+      }),
+    ) {
+      Err(ContractError::TokenNotAccepted {}) => {}
+      _ => unreachable!(),
+    }
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("token", &[]),
+      ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "user1".to_string(),
+        amount: Uint128::new(99),
+        msg: join_msg.clone(),
+      }),
+    ) {
+      Err(ContractError::InsufficientFee { .. }) => {}
+      _ => unreachable!(),
+    }
+
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("token", &[]),
+      ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "user1".to_string(),
+        amount: Uint128::new(100),
+        msg: join_msg,
+      }),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "registered".to_string());
+
+    // Person is registered and joins the city with single transfer, overpayment is refunded
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("token", &[]),
+      ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "user2".to_string(),
+        amount: Uint128::new(120),
+        msg: to_binary(&ReceiveMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: "user2".to_string(),
+          email: None,
+          city_id: 0,
+        })
+        .unwrap(),
+      }),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "registered".to_string());
+    assert_eq!(
+      res.messages[0].msg,
+      CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "token".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+          recipient: "user2".to_string(),
+          amount: Uint128::new(20),
+        })
+        .unwrap(),
+        funds: vec![],
+      })
+    );
+
+    let treasury: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookCityTreasury { city_id: 0 }).unwrap()).unwrap();
+    assert_eq!(
+      treasury,
+      ResponseMsg::LookCityTreasury {
+        balances: vec![],
+        cw20_balances: vec![Cw20Coin {
+          address: "token".to_string(),
+          amount: Uint128::new(200),
+        }],
+      }
+    );
+
+    // Treasury in the token is withdrawn via cw20 transfer
+    let res = execute(
+      deps.as_mut(),
+      env,
+      mock_info("creator", &[]),
+      ExecuteMsg::WithdrawCityTreasury {
+        city_id: 0,
+        denom: Denom::Cw20(Addr::unchecked("token")),
+        amount: None,
+        recipient: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(
+      res.messages[0].msg,
+      CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "token".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+          recipient: "creator".to_string(),
+          amount: Uint128::new(200),
+        })
+        .unwrap(),
+        funds: vec![],
+      })
+    );
+  }
+
+  #[test]
+  fn city_names() {
+    let mut deps = mock_dependencies();
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    // Names differing in case and whitespace only are the same
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: " super   CITY ".to_string(),
+        power_level: 3,
+      },
+    ) {
+      Err(ContractError::CityNameTaken { city_name }) => assert_eq!(city_name, " super   CITY ".to_string()),
+      _ => panic!("Must return CityNameTaken error"),
+    }
+
+    let city: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCityByName {
+          name: "SUPER city".to_string(),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match city {
+      ResponseMsg::LookCityByName { city } => assert_eq!(city.id, 0),
+      _ => panic!("Must return LookCityByName response"),
+    }
+
+    // Renaming city releases its previous name
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "New City".to_string(),
+        power_level: 3,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Super City".to_string(),
+        power_level: 3,
+      },
+    )
+    .unwrap();
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "super city".to_string(),
+        power_level: 3,
+      },
+    ) {
+      Err(ContractError::CityNameTaken { .. }) => {}
+      _ => panic!("Must return CityNameTaken error"),
+    }
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateCity {
+        city_id: 0,
+        name: "NEW CITY".to_string(),
+        power_level: 4,
+      },
+    )
+    .unwrap();
+
+    // Deleting city releases its name
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ArchiveCity { city_id: 1 },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::DeleteCity { city_id: 1 },
+    )
+    .unwrap();
+    let deleted_city = query(
+      deps.as_ref(),
+      mock_env(),
+      QueryMsg::LookCityByName {
+        name: "Super City".to_string(),
+      },
+    );
+    assert!(deleted_city.is_err());
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "Super City".to_string(),
+        power_level: 3,
+      },
+    )
+    .unwrap();
+  }
+
+  #[test]
+  fn city_names_indexing() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+    for i in 0..=MAX_CITY_NAMES_INDEXED {
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        ExecuteMsg::RegisterCity {
+          name: format!("City {}", i),
+          power_level: 3,
+        },
+      )
+      .unwrap();
+    }
+
+    // Cities of contract version 1.0.0 have no name index
+    let names: Map<&str, u64> = Map::new("city_id_by_name");
+    for i in 0..=MAX_CITY_NAMES_INDEXED {
+      names.remove(deps.as_mut().storage, &format!("city {}", i));
+    }
+    start_city_names_indexing(deps.as_mut().storage).unwrap();
+
+    // Names are not looked up or reserved until indexing is finished
+    match query(
+      deps.as_ref(),
+      mock_env(),
+      QueryMsg::LookCityByName {
+        name: "City 0".to_string(),
+      },
+    ) {
+      Err(ContractError::CityNamesNotIndexed {}) => {}
+      _ => panic!("Must return CityNamesNotIndexed error"),
+    }
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "City 0".to_string(),
+        power_level: 3,
+      },
+    ) {
+      Err(ContractError::CityNamesNotIndexed {}) => {}
+      _ => panic!("Must return CityNamesNotIndexed error"),
+    }
+
+    let res = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("anyone", &[]),
+      ExecuteMsg::IndexCityNames {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "false".to_string());
+    let res = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("anyone", &[]),
+      ExecuteMsg::IndexCityNames {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "true".to_string());
+
+    let city: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCityByName {
+          name: format!("city {}", MAX_CITY_NAMES_INDEXED),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match city {
+      ResponseMsg::LookCityByName { city } => assert_eq!(city.id, MAX_CITY_NAMES_INDEXED),
+      _ => panic!("Must return LookCityByName response"),
+    }
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RegisterCity {
+        name: "City 0".to_string(),
+        power_level: 3,
+      },
+    ) {
+      Err(ContractError::CityNameTaken { .. }) => {}
+      _ => panic!("Must return CityNameTaken error"),
+    }
+
+    // Finished indexing is not restarted
+    let res = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("anyone", &[]),
+      ExecuteMsg::IndexCityNames {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "true".to_string());
+  }
+
+  #[test]
+  fn city_regions() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let unauthorized_creation = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::CreateRegion {
+        name: "Country".to_string(),
+        parent_id: None,
+      },
+    );
+    assert!(unauthorized_creation.is_err());
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CreateRegion {
+        name: "Country".to_string(),
+        parent_id: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CreateRegion {
+        name: "Region".to_string(),
+        parent_id: Some(0),
+      },
+    )
+    .unwrap();
+
+    // Regions contain cities only
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::CreateRegion {
+        name: "Subregion".to_string(),
+        parent_id: Some(1),
+      },
+    ) {
+      Err(ContractError::InvalidRegionParent {}) => {}
+      _ => panic!("Must return InvalidRegionParent error"),
+    }
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityRegion {
+        city_id: 0,
+        region_id: Some(0),
+      },
+    ) {
+      Err(ContractError::InvalidRegionParent {}) => {}
+      _ => panic!("Must return InvalidRegionParent error"),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityRegion {
+        city_id: 0,
+        region_id: Some(1),
+      },
+    )
+    .unwrap();
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RemoveRegion { region_id: 0 },
+    ) {
+      Err(ContractError::RegionNotEmpty {}) => {}
+      _ => panic!("Must return RegionNotEmpty error"),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 1970 },
+        nickname: "user1".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    // Population of city is counted in region and country
+    let path: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookRegionPath { region_id: 1 }).unwrap()).unwrap();
+    assert_eq!(
+      path,
+      ResponseMsg::LookRegionPath {
+        path: vec![
+          RegionResponse {
+            id: 1,
+            name: "Region".to_string(),
+            kind: RegionKind::Region,
+            parent_id: Some(0),
+            regions_count: 0,
+            cities_count: 1,
+            population: 1,
+            min_age: None,
+            max_age: None,
+          },
+          RegionResponse {
+            id: 0,
+            name: "Country".to_string(),
+            kind: RegionKind::Country,
+            parent_id: None,
+            regions_count: 1,
+            cities_count: 0,
+            population: 1,
+            min_age: None,
+            max_age: None,
+          },
+        ],
+      }
+    );
+
+    let children: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookRegionChildren {
+          region_id: None,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match children {
+      ResponseMsg::LookRegionChildren { regions } => {
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].id, 0);
+      }
+      _ => panic!("Must return LookRegionChildren response"),
+    }
+
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookRegionCities {
+          region_id: 1,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match cities {
+      ResponseMsg::LookRegionCities { cities } => {
+        assert_eq!(cities.len(), 1);
+        assert_eq!(cities[0].region_id, Some(1));
+        assert_eq!(cities[0].population, 1);
+      }
+      _ => panic!("Must return LookRegionCities response"),
+    }
+
+    // Age limits of region apply to its cities
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UpdateRegion {
+        region_id: 1,
+        name: "Region".to_string(),
+        min_age: None,
+        max_age: Some(30),
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::RegisterPerson {
+        birthday: Birthday { day: None, year: 1970 },
+        nickname: "user2".to_string(),
+        email: None,
+      },
+    )
+    .unwrap();
+    let old_person_joining = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    );
+    assert!(old_person_joining.is_err());
+
+    // Members over age limit of region are evicted
+    let res = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::EvictOverage {
+        city_id: 0,
+        start_id: 0,
+        limit: 10,
+      },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "1".to_string());
+
+    // Region is removed after its cities leave
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityRegion {
+        city_id: 0,
+        region_id: None,
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::RemoveRegion { region_id: 1 },
+    )
+    .unwrap();
+    let country: ResponseMsg =
+      from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::LookRegion { region_id: 0 }).unwrap()).unwrap();
+    match country {
+      ResponseMsg::LookRegion { region } => {
+        assert_eq!(region.regions_count, 0);
+        assert_eq!(region.population, 0);
+      }
+      _ => panic!("Must return LookRegion response"),
+    }
+  }
+
+  #[test]
+  fn city_profile() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    let profile = CityProfile {
+      description: Some("The most super city".to_string()),
+      country_code: Some("NL".to_string()),
+      coordinates: Some(Coordinates {
+        latitude: 52_370_216,
+        longitude: 4_895_168,
+      }),
+      website: Some("https://super.city".to_string()),
+      image_uri: Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string()),
+      founded_on: Some(FoundingDate {
+        year: 1275,
+        month: Some(10),
+        day: Some(27),
+      }),
+    };
+
+    let unauthorized_setting = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: profile.clone(),
+      },
+    );
+    assert!(unauthorized_setting.is_err());
+
+    let mut bad_profile = profile.clone();
+    bad_profile.description = Some(" ".to_string());
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidCityDescription { .. }) => {}
+      _ => panic!("Must return InvalidCityDescription error"),
+    }
+
+    let mut bad_profile = profile.clone();
+    bad_profile.country_code = Some("nl".to_string());
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidCountryCode {}) => {}
+      _ => panic!("Must return InvalidCountryCode error"),
+    }
+
+    let mut bad_profile = profile.clone();
+    bad_profile.coordinates = Some(Coordinates {
+      latitude: 90_000_001,
+      longitude: 0,
+    });
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidCoordinates {}) => {}
+      _ => panic!("Must return InvalidCoordinates error"),
+    }
+
+    let mut bad_profile = profile.clone();
+    bad_profile.website = Some("ftp://super.city".to_string());
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidWebsite { .. }) => {}
+      _ => panic!("Must return InvalidWebsite error"),
+    }
+
+    let mut bad_profile = profile.clone();
+    bad_profile.image_uri = Some("super city.png".to_string());
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidImageUri { .. }) => {}
+      _ => panic!("Must return InvalidImageUri error"),
+    }
+
+    // 1900 is not leap year
+    let mut bad_profile = profile.clone();
+    bad_profile.founded_on = Some(FoundingDate {
+      year: 1900,
+      month: Some(2),
+      day: Some(29),
+    });
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidFoundingDate {}) => {}
+      _ => panic!("Must return InvalidFoundingDate error"),
+    }
+
+    let mut bad_profile = profile.clone();
+    bad_profile.founded_on = Some(FoundingDate {
+      year: 2023,
+      month: None,
+      day: None,
+    });
+    match execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: bad_profile,
+      },
+    ) {
+      Err(ContractError::InvalidFoundingDate {}) => {}
+      _ => panic!("Must return InvalidFoundingDate error"),
+    }
+
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: profile.clone(),
+      },
+    )
+    .unwrap();
+
+    let city: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCityByName {
+          name: "Super City".to_string(),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match city {
+      ResponseMsg::LookCityByName { city } => assert_eq!(city.profile, profile),
+      _ => panic!("Must return LookCityByName response"),
+    }
+  }
+
+  #[test]
+  fn cities_geo_queries() {
+    let mut deps = mock_dependencies();
+
+    let cities = vec![
+      ("Amsterdam", 52_370_216, 4_895_168),
+      ("Rotterdam", 51_922_500, 4_479_170),
+      ("Paris", 48_856_600, 2_352_200),
+      ("Berlin", 52_520_000, 13_405_000),
+      ("Suva", -18_141_600, 178_441_900),
+      ("Apia", -13_833_300, -171_766_700),
+    ];
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(
+      cities
+        .iter()
+        .map(|(name, _, _)| CitySeed {
+          name: name.to_string(),
+          power_level: 3,
+        })
+        .chain(vec![CitySeed {
+          name: "Nowhere".to_string(),
+          power_level: 3,
+        }])
+        .collect(),
+    );
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+    for (city_id, (_, latitude, longitude)) in cities.into_iter().enumerate() {
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        ExecuteMsg::SetCityProfile {
+          city_id: city_id as u64,
+          profile: CityProfile {
+            coordinates: Some(Coordinates { latitude, longitude }),
+            ..CityProfile::default()
+          },
+        },
+      )
+      .unwrap();
+    }
+
+    // Geohash is calculated with integer arithmetic
+    let amsterdam = Coordinates {
+      latitude: 52_370_216,
+      longitude: 4_895_168,
+    };
+    assert_eq!(amsterdam.geohash(9), "u173zmsw9".to_string());
+
+    let city_ids = |res: Binary| match from_binary(&res).unwrap() {
+      ResponseMsg::CitiesInArea { cities, .. } | ResponseMsg::NearestCities { cities, .. } => {
+        cities.into_iter().map(|city| city.id).collect::<Vec<u64>>()
+      }
+      _ => panic!("Must return list of cities"),
+    };
+
+    // Cities in area are ordered by geohash
+    let netherlands = |start_after: Option<Binary>, limit: u64| QueryMsg::CitiesInArea {
+      min_lat: 50_750_000,
+      max_lat: 53_550_000,
+      min_lon: 3_350_000,
+      max_lon: 7_230_000,
+      limit,
+      start_after,
+    };
+    let res = query(deps.as_ref(), mock_env(), netherlands(None, 10)).unwrap();
+    assert_eq!(city_ids(res), vec![1, 0]);
+    let next_key = match from_binary(&query(deps.as_ref(), mock_env(), netherlands(None, 1)).unwrap()).unwrap() {
+      ResponseMsg::CitiesInArea { cities, next_key } => {
+        assert_eq!(cities.into_iter().map(|city| city.id).collect::<Vec<u64>>(), vec![1]);
+        next_key
+      }
+      _ => panic!("Must return CitiesInArea response"),
+    };
+    assert!(next_key.is_some());
+    let res = query(deps.as_ref(), mock_env(), netherlands(next_key, 10)).unwrap();
+    assert_eq!(
+      from_binary::<ResponseMsg>(&res).unwrap(),
+      ResponseMsg::CitiesInArea {
+        cities: vec![city_response(0, get_city(&deps.storage, 0).unwrap())],
+        next_key: None,
+      }
+    );
+
+    // The whole world is covered by single cell
+    let res = query(
+      deps.as_ref(),
+      mock_env(),
+      QueryMsg::CitiesInArea {
+        min_lat: -90_000_000,
+        max_lat: 90_000_000,
+        min_lon: -180_000_000,
+        max_lon: 180_000_000,
+        limit: 10,
+        start_after: None,
+      },
+    )
+    .unwrap();
+    assert_eq!(city_ids(res).len(), 6);
+
+    let bad_area = query(
+      deps.as_ref(),
+      mock_env(),
+      QueryMsg::CitiesInArea {
+        min_lat: 53_550_000,
+        max_lat: 50_750_000,
+        min_lon: 3_350_000,
+        max_lon: 7_230_000,
+        limit: 10,
+        start_after: None,
+      },
+    );
+    assert!(bad_area.is_err());
+
+    // Utrecht is closer to Amsterdam than to Rotterdam
+    let utrecht = |limit: u64| QueryMsg::NearestCities {
+      latitude: 52_090_700,
+      longitude: 5_121_400,
+      limit,
+    };
+    let res = query(deps.as_ref(), mock_env(), utrecht(3)).unwrap();
+    assert_eq!(city_ids(res), vec![0, 1, 2]);
+
+    // Neighbourhood is wrapped over antimeridian
+    let res = query(
+      deps.as_ref(),
+      mock_env(),
+      QueryMsg::NearestCities {
+        latitude: -16_000_000,
+        longitude: -179_500_000,
+        limit: 2,
+      },
+    )
+    .unwrap();
+    assert_eq!(city_ids(res), vec![4, 5]);
+
+    // Neighbourhood is not widened over the whole world
+    let res = query(
+      deps.as_ref(),
+      mock_env(),
+      QueryMsg::NearestCities {
+        latitude: 0,
+        longitude: -140_000_000,
+        limit: 1,
+      },
+    )
+    .unwrap();
+    assert_eq!(city_ids(res), Vec::<u64>::new());
+
+    // City is moved in coordinates index with its profile
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityProfile {
+        city_id: 0,
+        profile: CityProfile::default(),
+      },
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), mock_env(), netherlands(None, 10)).unwrap();
+    assert_eq!(city_ids(res), vec![1]);
+    let res = query(deps.as_ref(), mock_env(), utrecht(1)).unwrap();
+    assert_eq!(city_ids(res), vec![1]);
+  }
+
+  #[test]
+  fn cities_geo_scan_limits() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
+
+    // Dense cluster of cities is followed by Amsterdam in coordinates index
+    let mut coordinates = (0..300)
+      .map(|i| Coordinates {
+        latitude: 500_000,
+        longitude: 10_000_000 + i,
+      })
+      .collect::<Vec<Coordinates>>();
+    coordinates.push(Coordinates {
+      latitude: 52_370_216,
+      longitude: 4_895_168,
+    });
+    for (city_id, coordinates) in coordinates.into_iter().enumerate() {
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        ExecuteMsg::RegisterCity {
+          name: format!("City {}", city_id),
+          power_level: 3,
+        },
+      )
+      .unwrap();
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        ExecuteMsg::SetCityProfile {
+          city_id: city_id as u64,
+          profile: CityProfile {
+            coordinates: Some(coordinates),
+            ..CityProfile::default()
+          },
+        },
+      )
+      .unwrap();
+    }
+
+    // Area cells cover the cluster, so scan is continued by the next page
+    let meridian = |start_after: Option<Binary>| QueryMsg::CitiesInArea {
+      min_lat: -1_000_000,
+      max_lat: 53_000_000,
+      min_lon: 4_000_000,
+      max_lon: 5_000_000,
+      limit: 10,
+      start_after,
+    };
+    let next_key = match from_binary(&query(deps.as_ref(), mock_env(), meridian(None)).unwrap()).unwrap() {
+      ResponseMsg::CitiesInArea { cities, next_key } => {
+        assert!(cities.is_empty());
+        next_key
+      }
+      _ => panic!("Must return CitiesInArea response"),
+    };
+    assert!(next_key.is_some());
+    match from_binary(&query(deps.as_ref(), mock_env(), meridian(next_key)).unwrap()).unwrap() {
+      ResponseMsg::CitiesInArea { cities, next_key } => {
+        assert_eq!(cities.into_iter().map(|city| city.id).collect::<Vec<u64>>(), vec![300]);
+        assert_eq!(next_key, None);
+      }
+      _ => panic!("Must return CitiesInArea response"),
+    }
+
+    // Neighbourhood of the cluster is too dense to be widened enough for 100 cities
+    let cluster = |limit: u64| QueryMsg::NearestCities {
+      latitude: 500_000,
+      longitude: 10_000_150,
+      limit,
+    };
+    match from_binary(&query(deps.as_ref(), mock_env(), cluster(1)).unwrap()).unwrap() {
+      ResponseMsg::NearestCities { cities, is_complete } => {
+        assert_eq!(cities.into_iter().map(|city| city.id).collect::<Vec<u64>>(), vec![150]);
+        assert!(is_complete);
+      }
+      _ => panic!("Must return NearestCities response"),
+    }
+    match from_binary(&query(deps.as_ref(), mock_env(), cluster(100)).unwrap()).unwrap() {
+      ResponseMsg::NearestCities { cities, is_complete } => {
+        assert!(!cities.is_empty());
+        assert!(!is_complete);
+      }
+      _ => panic!("Must return NearestCities response"),
+    }
+  }
+
+  #[test]
+  fn city_merge() {
+    let mut deps = mock_dependencies();
+
+    let mut msg = mock_instantiate_msg();
+    msg.city_deletion_batch_size = Some(2);
+    msg.seed_cities = Some(vec![
+      CitySeed {
+        name: "From City".to_string(),
+        power_level: 3,
+      },
+      CitySeed {
+        name: "Into City".to_string(),
+        power_level: 3,
+      },
+      CitySeed {
+        name: "Other City".to_string(),
+        power_level: 3,
+      },
+    ]);
+    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+    for user in ["user1", "user2", "user3", "user4", "user5", "user6"] {
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
+    for (user, city_id) in [("user1", 0), ("user2", 0), ("user3", 0), ("user4", 0), ("user1", 1)] {
+      execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+    }
+
+    // user5 is waiting for free slot
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 0,
+        max_members: Some(4),
+      },
+    )
+    .unwrap();
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user5", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    // user3 is banned from the city merged into, user2 requested to join it paying join fee
+    for msg in [
+      ExecuteMsg::BanFromCity {
+        city_id: 1,
+        person: Addr::unchecked("user3"),
+        until: Expiration::Never {},
+      },
+      ExecuteMsg::SetJoinFee {
+        city_id: 1,
+        join_fee: Some(coin(100, "uatom")),
+      },
+      ExecuteMsg::SetJoinPolicy {
+        city_id: 1,
+        join_policy: JoinPolicy::ApprovalRequired,
+      },
+    ] {
+      execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+    }
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user2", &coins(100, "uatom")),
+      ExecuteMsg::RegisterInCity {
+        city_id: 1,
+        invite_code: None,
+      },
+    )
+    .unwrap();
+
+    let unauthorized_merge = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user1", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 1 },
+    );
+    assert!(unauthorized_merge.is_err());
+
+    let bad_merge = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 0 },
+    );
+    assert!(bad_merge.is_err());
+
+    // The city merged into should have free slots for all members
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 1,
+        max_members: Some(4),
+      },
+    )
+    .unwrap();
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 1 },
+    ) {
+      Err(ContractError::CityCapacityExceeded { city_name }) => assert_eq!(city_name, "Into City".to_string()),
+      _ => panic!("Must return CityCapacityExceeded error"),
+    }
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 1,
+        max_members: Some(5),
+      },
+    )
+    .unwrap();
+
+    // Waiting person is removed and the last member is moved by the first batch
+    let res = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "3".to_string());
+
+    let merging_city_joining = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user6", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    );
+    assert!(merging_city_joining.is_err());
+
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user6", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 2 },
+    ) {
+      Err(ContractError::CityMerged { .. }) => {}
+      _ => panic!("Must return CityMerged error"),
+    }
+
+    // The city merged into is not able to be archived or merged until the merge finishes
+    for msg in [
+      ExecuteMsg::ArchiveCity { city_id: 1 },
+      ExecuteMsg::MergeCities { from: 1, into: 2 },
+    ] {
+      match execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg) {
+        Err(ContractError::CityMergeInProgress { .. }) => {}
+        _ => panic!("Must return CityMergeInProgress error"),
+      }
+    }
+
+    // Banned member stops the merge until the ban is lifted
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user6", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 1 },
+    ) {
+      Err(ContractError::MemberNotMovable { nickname, city_name }) => {
+        assert_eq!(nickname, "user3".to_string());
+        assert_eq!(city_name, "Into City".to_string());
+      }
+      _ => panic!("Must return MemberNotMovable error"),
+    }
+    execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::UnbanFromCity {
+        city_id: 1,
+        person: Addr::unchecked("user3"),
+      },
+    )
+    .unwrap();
+
+    // Anyone is able to continue merge, moved member's join request is removed and join fee is refunded
+    let res = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user6", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "1".to_string());
+    let requests: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCityJoinRequests {
+          city_id: 1,
+          start_after: None,
+          limit: 10,
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(requests, ResponseMsg::LookCityJoinRequests { requests: vec![] });
+    let refunds: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookRefunds {
+          person: Addr::unchecked("user2"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
     assert_eq!(
-      maintainer,
-      ResponseMsg::LookMaintainer {
-        maintainer: Addr::unchecked("creator")
+      refunds,
+      ResponseMsg::LookRefunds {
+        refunds: coins(100, "uatom"),
+        cw20_refunds: vec![],
       }
     );
-  }
 
-  #[test]
-  fn maintainer_takeover() {
-    let mut deps = mock_dependencies();
+    let res = execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user6", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0].value, "0".to_string());
 
-    let mut env = mock_env();
-    env.block.height = 887;
-    env.block.time = Timestamp::from_seconds(YEAR_IN_SECONDS * 52 + YEAR_IN_SECONDS / 2); // in middle of 2022
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCities { start_id: 0, limit: 2 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match cities {
+      ResponseMsg::LookCities { cities } => {
+        assert_eq!(cities[0].status, CityStatus::Archived);
+        assert_eq!(cities[0].merged_into, Some(1));
+        assert_eq!(cities[0].population, 0);
+        assert_eq!(cities[0].waitlist_length, 0);
+        // user1 living in both cities is counted once
+        assert_eq!(cities[1].population, 4);
+        assert_eq!(cities[1].waitlist_length, 0);
+      }
+      _ => panic!("Must return LookCities response"),
+    }
 
-    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg {}).unwrap();
+    let person: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookPerson {
+          person: Addr::unchecked("user1"),
+        },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match person {
+      ResponseMsg::LookPerson { person } => assert_eq!(person.resident_times, 1),
+      _ => panic!("Must return LookPerson response"),
+    }
 
-    execute(
-      deps.as_mut(),
-      env.clone(),
-      mock_info("user_1", &[]),
-      ExecuteMsg::RegisterPerson {
-        birthday: Birthday { day: None, year: 2015 },
-        nickname: "super_user".to_string(),
-        email: None,
-      },
+    // Queries follow merged city to the city it was merged into
+    let city: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCityByName {
+          name: "From City".to_string(),
+        },
+      )
+      .unwrap(),
     )
     .unwrap();
-    execute(
-      deps.as_mut(),
-      env.clone(),
-      mock_info("user_2", &[]),
-      ExecuteMsg::RegisterPerson {
-        birthday: Birthday { day: None, year: 2000 },
-        nickname: "super_user".to_string(),
-        email: None,
-      },
+    match city {
+      ResponseMsg::LookCityByName { city } => assert_eq!(city.id, 1),
+      _ => panic!("Must return LookCityByName response"),
+    }
+    let people: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::LookCityPeople {
+          city: 0,
+          start_id: 0,
+          limit: 10,
+        },
+      )
+      .unwrap(),
     )
     .unwrap();
+    match people {
+      ResponseMsg::LookCityPeople { people } => assert_eq!(people.len(), 4),
+      _ => panic!("Must return LookCityPeople response"),
+    }
+
+    match execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::MergeCities { from: 0, into: 2 },
+    ) {
+      Err(ContractError::CityMerged { .. }) => {}
+      _ => panic!("Must return CityMerged error"),
+    }
+
     execute(
       deps.as_mut(),
-      env.clone(),
-      mock_info("user_3", &[]),
-      ExecuteMsg::RegisterPerson {
-        birthday: Birthday { day: None, year: 2015 },
-        nickname: "Super_Maintainer_887".to_string(),
-        email: None,
-      },
+      mock_env(),
+      mock_info("creator", &[]),
+      ExecuteMsg::ArchiveCity { city_id: 1 },
     )
     .unwrap();
+  }
+
+  #[test]
+  fn city_bans() {
+    let mut deps = mock_dependencies();
+
+    let mut env = mock_env();
+    env.block.time = Timestamp::from_seconds(1_650_000_000);
+
+    let mut msg = mock_instantiate_msg();
+    msg.seed_cities = Some(vec![CitySeed {
+      name: "Super City".to_string(),
+      power_level: 3,
+    }]);
+    instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+    for user in ["user1", "user2"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterPerson {
+          birthday: Birthday { day: None, year: 1970 },
+          nickname: user.to_string(),
+          email: None,
+        },
+      )
+      .unwrap();
+    }
     execute(
       deps.as_mut(),
       env.clone(),
-      mock_info("user_4", &[]),
-      ExecuteMsg::RegisterPerson {
-        birthday: Birthday { day: None, year: 2004 },
-        nickname: "Super_Maintainer_887".to_string(),
-        email: None,
+      mock_info("creator", &[]),
+      ExecuteMsg::SetCityCapacity {
+        city_id: 0,
+        max_members: Some(1),
       },
     )
     .unwrap();
+    for user in ["user1", "user2"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user, &[]),
+        ExecuteMsg::RegisterInCity {
+          city_id: 0,
+          invite_code: None,
+        },
+      )
+      .unwrap();
+    }
 
-    let bad_takeover = execute(
+    let until = Expiration::AtTime(env.block.time.plus_seconds(100));
+    let unauthorized_ban = execute(
       deps.as_mut(),
       env.clone(),
-      mock_info("creator", &[]),
-      ExecuteMsg::BecomeMaintainer {},
+      mock_info("user2", &[]),
+      ExecuteMsg::BanFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user1"),
+        until,
+      },
     );
-    assert!(bad_takeover.is_err());
+    assert!(unauthorized_ban.is_err());
 
-    let bad_takeover = execute(
+    let expired_ban = execute(
       deps.as_mut(),
       env.clone(),
-      mock_info("user_1", &[]),
-      ExecuteMsg::BecomeMaintainer {},
+      mock_info("creator", &[]),
+      ExecuteMsg::BanFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user1"),
+        until: Expiration::AtTime(env.block.time),
+      },
     );
-    assert!(bad_takeover.is_err());
+    assert!(expired_ban.is_err());
 
-    let bad_takeover = execute(
-      deps.as_mut(),
-      env.clone(),
-      mock_info("user_2", &[]),
-      ExecuteMsg::BecomeMaintainer {},
-    );
-    assert!(bad_takeover.is_err());
+    // Banned people leave the city and its waitlist
+    for user in ["user2", "user1"] {
+      execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("creator", &[]),
+        ExecuteMsg::BanFromCity {
+          city_id: 0,
+          person: Addr::unchecked(user),
+          until,
+        },
+      )
+      .unwrap();
+    }
+    let cities: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCities { start_id: 0, limit: 1 },
+      )
+      .unwrap(),
+    )
+    .unwrap();
+    match cities {
+      ResponseMsg::LookCities { cities } => {
+        assert_eq!(cities[0].population, 0);
+        assert_eq!(cities[0].waitlist_length, 0);
+      }
+      _ => panic!("Must return LookCities response"),
+    }
 
-    let bad_takeover = execute(
+    match execute(
       deps.as_mut(),
       env.clone(),
-      mock_info("user_3", &[]),
-      ExecuteMsg::BecomeMaintainer {},
-    );
-    assert!(bad_takeover.is_err());
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
+    ) {
+      Err(ContractError::BannedFromCity {
+        until: banned_until, ..
+      }) => assert_eq!(banned_until, until),
+      _ => panic!("Must return BannedFromCity error"),
+    }
 
-    execute(
-      deps.as_mut(),
-      env.clone(),
-      mock_info("user_4", &[]),
-      ExecuteMsg::BecomeMaintainer {},
+    let bans: ResponseMsg = from_binary(
+      &query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::LookCityBans {
+          city_id: 0,
+          start_after: Some(Addr::unchecked("user1")),
+          limit: 10,
+        },
+      )
+      .unwrap(),
     )
     .unwrap();
-
-    let maintainer: ResponseMsg =
-      from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::LookMaintainer {}).unwrap()).unwrap();
     assert_eq!(
-      maintainer,
-      ResponseMsg::LookMaintainer {
-        maintainer: Addr::unchecked("user_4")
+      bans,
+      ResponseMsg::LookCityBans {
+        bans: vec![CityBanResponse {
+          person: Addr::unchecked("user2"),
+          until,
+        }],
       }
     );
-  }
-
-  #[test]
-  fn city_creation() {
-    let mut deps = mock_dependencies();
 
-    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg {}).unwrap();
+    let ban_status = |deps: Deps, env: Env, person: &str| -> ResponseMsg {
+      from_binary(
+        &query(
+          deps,
+          env,
+          QueryMsg::LookBanStatus {
+            city_id: 0,
+            person: Addr::unchecked(person),
+          },
+        )
+        .unwrap(),
+      )
+      .unwrap()
+    };
+    assert_eq!(
+      ban_status(deps.as_ref(), env.clone(), "user1"),
+      ResponseMsg::LookBanStatus {
+        banned: true,
+        until: Some(until),
+      }
+    );
 
+    // Unbanned person is able to join the city
     execute(
       deps.as_mut(),
-      mock_env(),
+      env.clone(),
       mock_info("creator", &[]),
-      ExecuteMsg::RegisterCity {
-        name: "Super City".to_string(),
-        power_level: 5,
+      ExecuteMsg::UnbanFromCity {
+        city_id: 0,
+        person: Addr::unchecked("user2"),
       },
     )
     .unwrap();
-
-    let bad_city_creation = execute(
-      deps.as_mut(),
-      mock_env(),
-      mock_info("mad", &[]),
-      ExecuteMsg::RegisterCity {
-        name: "Mad City".to_string(),
-        power_level: 7,
-      },
-    );
-    assert!(bad_city_creation.is_err());
-
     execute(
       deps.as_mut(),
-      mock_env(),
-      mock_info("creator", &[]),
-      ExecuteMsg::RegisterCity {
-        name: "Secret City".to_string(),
-        power_level: 3,
+      env.clone(),
+      mock_info("user2", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
       },
     )
     .unwrap();
 
-    let cities: ResponseMsg = from_binary(
-      &query(
-        deps.as_ref(),
-        mock_env(),
-        QueryMsg::LookCities { start_id: 0, limit: 10 },
-      )
-      .unwrap(),
+    // Expired ban is removed on joining
+    env.block.time = env.block.time.plus_seconds(100);
+    assert_eq!(
+      ban_status(deps.as_ref(), env.clone(), "user1"),
+      ResponseMsg::LookBanStatus {
+        banned: false,
+        until: Some(until),
+      }
+    );
+    execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info("user1", &[]),
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
     )
     .unwrap();
     assert_eq!(
-      cities,
-      ResponseMsg::LookCities {
-        cities: [
-          CityResponse {
-            id: 0,
-            name: "Super City".to_string(),
-            power_level: 5,
-            population: 0
-          },
-          CityResponse {
-            id: 1,
-            name: "Secret City".to_string(),
-            power_level: 3,
-            population: 0
-          },
-        ]
-        .to_vec()
+      ban_status(deps.as_ref(), env.clone(), "user1"),
+      ResponseMsg::LookBanStatus {
+        banned: false,
+        until: None,
       }
     );
   }
@@ -578,7 +9033,13 @@ mod tests {
     env.block.height = 887;
     env.block.time = Timestamp::from_seconds(YEAR_IN_SECONDS * 52 + YEAR_IN_SECONDS / 2); // in middle of 2022
 
-    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg {}).unwrap();
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
 
     let bad_person_registration = execute(
       deps.as_mut(),
@@ -675,7 +9136,13 @@ mod tests {
   fn person_updation() {
     let mut deps = mock_dependencies();
 
-    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg {}).unwrap();
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
 
     let bad_person_updation = execute(
       deps.as_mut(),
@@ -764,7 +9231,13 @@ mod tests {
   fn person_in_city_registration() {
     let mut deps = mock_dependencies();
 
-    instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg {}).unwrap();
+    instantiate(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("creator", &[]),
+      mock_instantiate_msg(),
+    )
+    .unwrap();
     execute(
       deps.as_mut(),
       mock_env(),
@@ -813,7 +9286,10 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user_1", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 0 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
     )
     .unwrap();
 
@@ -821,7 +9297,10 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 0 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
     );
     assert!(bad_registring_in_city.is_err());
 
@@ -829,14 +9308,20 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user_2", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 0 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
     )
     .unwrap();
     execute(
       deps.as_mut(),
       mock_env(),
       mock_info("user_2", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 1 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 1,
+        invite_code: None,
+      },
     )
     .unwrap();
 
@@ -923,7 +9408,17 @@ mod tests {
           id: 0,
           name: "Super City".to_string(),
           power_level: 3,
-          population: 2
+          population: 2,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         }]
         .to_vec()
       }
@@ -950,13 +9445,33 @@ mod tests {
             id: 0,
             name: "Super City".to_string(),
             power_level: 3,
-            population: 2
+            population: 2,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
           },
           CityResponse {
             id: 1,
             name: "Secret City".to_string(),
             power_level: 3,
-            population: 1
+            population: 1,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
           },
         ]
         .to_vec()
@@ -967,7 +9482,10 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user_1", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 0 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
     );
     assert!(bad_registring_in_city.is_err());
 
@@ -982,7 +9500,10 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user_1", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 0 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 0,
+        invite_code: None,
+      },
     )
     .unwrap();
 
@@ -1069,7 +9590,17 @@ mod tests {
           id: 0,
           name: "Super City".to_string(),
           power_level: 3,
-          population: 2
+          population: 2,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         }]
         .to_vec()
       }
@@ -1096,13 +9627,33 @@ mod tests {
             id: 0,
             name: "Super City".to_string(),
             power_level: 3,
-            population: 2
+            population: 2,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
           },
           CityResponse {
             id: 1,
             name: "Secret City".to_string(),
             power_level: 3,
-            population: 1
+            population: 1,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
           },
         ]
         .to_vec()
@@ -1188,7 +9739,17 @@ mod tests {
           id: 0,
           name: "Super City".to_string(),
           power_level: 3,
-          population: 2
+          population: 2,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         }]
         .to_vec()
       }
@@ -1214,7 +9775,17 @@ mod tests {
           id: 0,
           name: "Super City".to_string(),
           power_level: 3,
-          population: 2
+          population: 2,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         }]
         .to_vec()
       }
@@ -1240,7 +9811,10 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user_1", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 3 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 3,
+        invite_code: None,
+      },
     );
     assert!(bad_registring_in_city.is_err());
 
@@ -1248,7 +9822,10 @@ mod tests {
       deps.as_mut(),
       mock_env(),
       mock_info("user_1", &[]),
-      ExecuteMsg::RegisterInCity { city_id: 1 },
+      ExecuteMsg::RegisterInCity {
+        city_id: 1,
+        invite_code: None,
+      },
     )
     .unwrap();
     execute(
@@ -1343,7 +9920,17 @@ mod tests {
           id: 0,
           name: "Super City".to_string(),
           power_level: 3,
-          population: 1
+          population: 1,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         }]
         .to_vec()
       }
@@ -1366,13 +9953,33 @@ mod tests {
             id: 0,
             name: "Super City".to_string(),
             power_level: 3,
-            population: 1
+            population: 1,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
           },
           CityResponse {
             id: 1,
             name: "Secret City".to_string(),
             power_level: 3,
-            population: 0
+            population: 0,
+            status: CityStatus::Active,
+            join_policy: JoinPolicy::Open,
+            max_members: None,
+            waitlist_length: 0,
+            min_age: None,
+            max_age: None,
+            join_fee: None,
+            region_id: None,
+            profile: CityProfile::default(),
+            merged_into: None
           },
         ]
         .to_vec()
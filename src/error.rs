@@ -1,6 +1,7 @@
 // The file is responsible for storing list of custom error types
 
-use cosmwasm_std::{StdError, StdResult};
+use cosmwasm_std::{StdError, StdResult, Uint128};
+use cw_utils::{Expiration, PaymentError};
 use thiserror::Error;
 
 use crate::utils::{CityName, Nickname};
@@ -27,6 +28,20 @@ pub enum ContractError {
   #[error("{0}")]
   Std(#[from] StdError),
 
+  // Semver() is used for wrapping invalid contract version error to ContractError
+  #[error("{0}")]
+  Semver(#[from] semver::Error),
+
+  // Payment() is used for wrapping invalid funds error to ContractError
+  #[error("{0}")]
+  Payment(#[from] PaymentError),
+
+  #[error("Contract could not be migrated from another contract ({contract})")]
+  InvalidMigration { contract: String },
+
+  #[error("Contract could not be downgraded (stored: {stored}, current: {current})")]
+  CannotDowngrade { stored: String, current: String },
+
   #[error("Inconsistent input data provided")]
   InconsistentData {},
 
@@ -36,10 +51,181 @@ pub enum ContractError {
   #[error("You are already maintainer")]
   AlreadyMaintainer {},
 
+  #[error("Contract is frozen")]
+  Frozen {},
+
+  #[error("You don't have required role ({role})")]
+  MissingRole { role: String },
+
+  #[error("You are neither maintainer nor guardian")]
+  NotGuardian {},
+
+  #[error("You are neither maintainer nor city admin")]
+  NotCityAdmin {},
+
+  #[error("You are neither maintainer nor city mayor")]
+  NotMayor {},
+
+  #[error("Address already holds city office")]
+  AlreadyCityOfficial {},
+
+  #[error("Address is not city deputy")]
+  NotDeputy {},
+
+  #[error("City has too many deputies (max: {max})")]
+  TooManyDeputies { max: u64 },
+
+  #[error("Election has too many candidates (max: {max})")]
+  TooManyCandidates { max: u64 },
+
+  #[error("Action is paused (action: {action:?}, reason: {reason:?})")]
+  Paused { action: String, reason: String },
+
+  #[error("Action is not paused (action: {action:?})")]
+  NotPaused { action: String },
+
+  #[error("Action requires council approval")]
+  CouncilApprovalRequired {},
+
+  #[error("You are not council member")]
+  NotCouncilMember {},
+
+  #[error("Action could not be proposed to council")]
+  NotPrivilegedAction {},
+
+  #[error("Council proposal is expired")]
+  CouncilProposalExpired {},
+
+  #[error("Council proposal is already approved by you")]
+  AlreadyApproved {},
+
+  #[error("Council proposal is not approved by you")]
+  NotApproved {},
+
+  #[error("Council proposal has not enough approvals ({approvals}/{threshold})")]
+  NotEnoughApprovals { approvals: u64, threshold: u64 },
+
+  #[error("Election voting period is over")]
+  VotingClosed {},
+
+  #[error("Election voting period is not over yet")]
+  VotingNotOver {},
+
+  #[error("Election is already tallied")]
+  ElectionAlreadyTallied {},
+
+  #[error("Election is not tallied")]
+  ElectionNotTallied {},
+
+  #[error("Candidate is already nominated")]
+  AlreadyNominated {},
+
+  #[error("Candidate is not nominated")]
+  NotNominated {},
+
+  #[error("You are not registered in any city")]
+  NoVotingPower {},
+
+  #[error("You are not proposed maintainer")]
+  NotMaintainerCandidate {},
+
+  #[error("Maintainer proposal is expired")]
+  MaintainerProposalExpired {},
+
+  #[error("Maintainer claim is already pending")]
+  MaintainerClaimPending {},
+
+  #[error("Maintainer claim challenge window is over")]
+  ChallengeWindowOver {},
+
+  #[error("Maintainer claim challenge window is not over yet")]
+  ChallengeWindowNotOver {},
+
   // Use { field } if you are sure that the field is not empty, results in 'error start field_value end'
   #[error("You don't satisfy maintainer requirements ({requirement})")]
   InconsistentMaintainer { requirement: String },
 
+  #[error("City is archived (city name: {city_name:?})")]
+  CityArchived { city_name: CityName },
+
+  #[error("City is not archived (city name: {city_name:?})")]
+  CityNotArchived { city_name: CityName },
+
+  #[error("City is merged into another city (city name: {city_name:?})")]
+  CityMerged { city_name: CityName },
+
+  #[error("Another city is being merged into the city (city name: {city_name:?})")]
+  CityMergeInProgress { city_name: CityName },
+
+  #[error("City has not enough free slots for members of merged city (city name: {city_name:?})")]
+  CityCapacityExceeded { city_name: CityName },
+
+  #[error("Member is not able to move to the city, it has to be kicked or restrictions lifted (nickname: {nickname:?}, city name: {city_name:?})")]
+  MemberNotMovable { nickname: Nickname, city_name: CityName },
+
+  #[error("Person is banned from the city (city name: {city_name:?}, until: {until})")]
+  BannedFromCity { city_name: CityName, until: Expiration },
+
+  #[error("Person is not invited to the city (city name: {city_name:?})")]
+  NotInvited { city_name: CityName },
+
+  #[error("Person age does not meet city limits (city name: {city_name:?})")]
+  AgeRestricted { city_name: CityName },
+
+  #[error("Person is already on city waitlist")]
+  AlreadyWaitlisted {},
+
+  #[error("Person is not on city waitlist")]
+  NotWaitlisted {},
+
+  #[error("Invite code is invalid")]
+  InvalidInviteCode {},
+
+  #[error("Invite code is expired")]
+  InviteCodeExpired {},
+
+  #[error("City description is blank or longer than {max_length} bytes")]
+  InvalidCityDescription { max_length: usize },
+
+  #[error("Country code should be ISO 3166-1 alpha-2 code in upper case")]
+  InvalidCountryCode {},
+
+  #[error("Coordinates are out of range")]
+  InvalidCoordinates {},
+
+  #[error("Website should be http or https URL up to {max_length} bytes")]
+  InvalidWebsite { max_length: usize },
+
+  #[error("Image URI should contain scheme and be up to {max_length} bytes")]
+  InvalidImageUri { max_length: usize },
+
+  #[error("Founding date is not a valid past date")]
+  InvalidFoundingDate {},
+
+  #[error("Region could not be placed under the parent")]
+  InvalidRegionParent {},
+
+  #[error("Region has child regions or cities")]
+  RegionNotEmpty {},
+
+  #[error("Join request is already pending")]
+  JoinRequestPending {},
+
+  #[error("Join request is expired")]
+  JoinRequestExpired {},
+
+  #[error("Join fee is not covered (required: {required})")]
+  InsufficientFee { required: Uint128 },
+
+  #[error("City accepts join fee in cw20 tokens only")]
+  NativeFeeNotAccepted {},
+
+  #[error("Token is not accepted by the city")]
+  TokenNotAccepted {},
+
+  #[error("Balance is too low to withdraw (balance: {balance})")]
+  InsufficientBalance { balance: Uint128 },
+
   #[error("{kind} with this identifier is not found")]
   NotFound { kind: String },
 
@@ -49,4 +235,10 @@ pub enum ContractError {
 
   #[error("Person is already registered in the city (nickname: {nickname:?}, city name: {city_name:?})")]
   PersonAlreadyRegisteredInCity { nickname: Nickname, city_name: CityName },
+
+  #[error("City name is already taken (city name: {city_name:?})")]
+  CityNameTaken { city_name: CityName },
+
+  #[error("Names of existing cities are not indexed yet, indexing has to be continued")]
+  CityNamesNotIndexed {},
 }
@@ -1,16 +1,41 @@
 // The file is responsible for storing all message structs
 
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Binary, Coin, Uint128};
+use cw20::{Cw20Coin, Cw20ReceiveMsg, Denom};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::utils::{Birthday, CityName, CityResponse, Email, Nickname, PersonResponse};
+use crate::utils::{
+  AdministeredCityResponse, Birthday, CandidateResponse, CityBanResponse, CityChangeResponse, CityName, CityProfile,
+  CityResponse, CitySeed, ConfigResponse, CouncilProposalResponse, CouncilResponse, ElectionResponse, Email,
+  InviteCodeResponse, JoinPolicy, JoinRequestResponse, MaintainerClaimResponse, MaintainerProposalResponse, Nickname,
+  PausedAction, PersonResponse, RegionName, RegionResponse, Role,
+};
 
 // Instantiate message
 // JSON: '{ "virtual_field": "virtual answer", etc. }'
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct InstantiateMsg {}
+pub struct InstantiateMsg {
+  pub maintainer: Option<Addr>,
+  pub maintainer_requirement_name: Option<Nickname>,
+  pub maintainer_requirement_age: Option<u8>,
+  pub maintainer_claim_window: Option<u64>,
+  pub election_voting_period: Option<u64>,
+  pub election_quorum: Option<u64>,
+  pub max_page_size: Option<u64>,
+  pub city_deletion_batch_size: Option<u64>,
+  pub join_request_period: Option<u64>,
+  pub protocol_fee_share: Option<u64>,
+  pub seed_cities: Option<Vec<CitySeed>>,
+}
+
+// Migrate message
+// JSON: '{}'
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {}
 
 // Execute message
 // JSON: '{ "message_type": { "virtual_field": "virtual answer", etc. } }'
@@ -21,6 +46,123 @@ pub enum ExecuteMsg {
     name: CityName,
     power_level: u8,
   },
+  UpdateCity {
+    city_id: u64,
+    name: CityName,
+    power_level: u8,
+  },
+  SetMayor {
+    city_id: u64,
+    mayor: Option<Addr>,
+  },
+  AppointDeputy {
+    city_id: u64,
+    deputy: Addr,
+  },
+  DismissDeputy {
+    city_id: u64,
+    deputy: Addr,
+  },
+  KickFromCity {
+    city_id: u64,
+    person: Addr,
+    reason: Option<String>,
+  },
+  BanFromCity {
+    city_id: u64,
+    person: Addr,
+    until: Expiration,
+  },
+  UnbanFromCity {
+    city_id: u64,
+    person: Addr,
+  },
+  SetCityProfile {
+    city_id: u64,
+    profile: CityProfile,
+  },
+  SetCityAgeLimits {
+    city_id: u64,
+    min_age: Option<u8>,
+    max_age: Option<u8>,
+  },
+  SetCityCapacity {
+    city_id: u64,
+    max_members: Option<u64>,
+  },
+  SetJoinPolicy {
+    city_id: u64,
+    join_policy: JoinPolicy,
+  },
+  InviteToCity {
+    city_id: u64,
+    person: Addr,
+  },
+  RevokeInvite {
+    city_id: u64,
+    person: Addr,
+  },
+  CreateInviteCode {
+    city_id: u64,
+    code_hash: String,
+    expires_at: Expiration,
+    uses: u64,
+  },
+  RevokeInviteCode {
+    city_id: u64,
+    code_hash: String,
+  },
+  ApproveJoinRequest {
+    city_id: u64,
+    person: Addr,
+  },
+  RejectJoinRequest {
+    city_id: u64,
+    person: Addr,
+  },
+  ArchiveCity {
+    city_id: u64,
+  },
+  DeleteCity {
+    city_id: u64,
+  },
+  MergeCities {
+    from: u64,
+    into: u64,
+  },
+  IndexCityNames {},
+  SetJoinFee {
+    city_id: u64,
+    join_fee: Option<Coin>,
+  },
+  SetCw20JoinFee {
+    city_id: u64,
+    token: Addr,
+    amount: Option<Uint128>,
+  },
+  WithdrawCityTreasury {
+    city_id: u64,
+    denom: Denom,
+    amount: Option<Uint128>,
+    recipient: Option<Addr>,
+  },
+  CreateRegion {
+    name: RegionName,
+    parent_id: Option<u64>,
+  },
+  UpdateRegion {
+    region_id: u64,
+    name: RegionName,
+    min_age: Option<u8>,
+    max_age: Option<u8>,
+  },
+  RemoveRegion {
+    region_id: u64,
+  },
+  SetCityRegion {
+    city_id: u64,
+    region_id: Option<u64>,
+  },
   RegisterPerson {
     birthday: Birthday,
     nickname: Nickname,
@@ -33,12 +175,304 @@ pub enum ExecuteMsg {
 
   RegisterInCity {
     city_id: u64,
+    invite_code: Option<String>,
   },
   UnregisterFromCity {
     city_id: u64,
   },
+  LeaveWaitlist {
+    city_id: u64,
+  },
+  EvictOverage {
+    city_id: u64,
+    start_id: u64,
+    limit: u64,
+  },
+  CancelJoinRequest {
+    city_id: u64,
+  },
+  ClaimRefund {
+    denom: Denom,
+  },
+  Receive(Cw20ReceiveMsg),
 
   BecomeMaintainer {},
+  VetoMaintainerClaim {},
+  FinalizeMaintainerClaim {},
+  ProposeMaintainer {
+    address: Addr,
+    expires_at: Expiration,
+  },
+  AcceptMaintainer {},
+  CancelMaintainerProposal {},
+  RenounceMaintainer {},
+  UpdateConfig {
+    maintainer_requirement_name: Option<Nickname>,
+    maintainer_requirement_age: Option<u8>,
+    election_voting_period: Option<u64>,
+    election_quorum: Option<u64>,
+    max_page_size: Option<u64>,
+    city_deletion_batch_size: Option<u64>,
+    join_request_period: Option<u64>,
+    protocol_fee_share: Option<u64>,
+  },
+  WithdrawProtocolFees {
+    denom: Denom,
+    amount: Option<Uint128>,
+    recipient: Option<Addr>,
+  },
+
+  Nominate {
+    candidate: Addr,
+  },
+  Vote {
+    candidate: Addr,
+  },
+  TallyElection {},
+  FinalizeElection {},
+
+  GrantRole {
+    address: Addr,
+    role: Role,
+  },
+  RevokeRole {
+    address: Addr,
+    role: Role,
+  },
+
+  SetGuardian {
+    guardian: Option<Addr>,
+  },
+  Pause {
+    action: Option<String>,
+    reason: String,
+  },
+  Unpause {
+    action: Option<String>,
+  },
+
+  SetCouncil {
+    members: Vec<Addr>,
+    threshold: u64,
+  },
+  CouncilPropose {
+    action: Box<ExecuteMsg>,
+    expires_at: Expiration,
+  },
+  CouncilApprove {
+    proposal_id: u64,
+  },
+  CouncilRevokeApproval {
+    proposal_id: u64,
+  },
+  CouncilExecute {
+    proposal_id: u64,
+  },
+}
+
+impl ExecuteMsg {
+  // Privileged messages require maintainer access level, they are proposed to council in council mode
+  pub fn is_privileged(&self) -> bool {
+    matches!(
+      self,
+      ExecuteMsg::RegisterCity { .. }
+        | ExecuteMsg::UpdateCity { .. }
+        | ExecuteMsg::SetMayor { .. }
+        | ExecuteMsg::AppointDeputy { .. }
+        | ExecuteMsg::DismissDeputy { .. }
+        | ExecuteMsg::KickFromCity { .. }
+        | ExecuteMsg::BanFromCity { .. }
+        | ExecuteMsg::UnbanFromCity { .. }
+        | ExecuteMsg::SetCityProfile { .. }
+        | ExecuteMsg::SetCityAgeLimits { .. }
+        | ExecuteMsg::SetCityCapacity { .. }
+        | ExecuteMsg::SetJoinPolicy { .. }
+        | ExecuteMsg::InviteToCity { .. }
+        | ExecuteMsg::RevokeInvite { .. }
+        | ExecuteMsg::CreateInviteCode { .. }
+        | ExecuteMsg::RevokeInviteCode { .. }
+        | ExecuteMsg::ApproveJoinRequest { .. }
+        | ExecuteMsg::RejectJoinRequest { .. }
+        | ExecuteMsg::ArchiveCity { .. }
+        | ExecuteMsg::DeleteCity { .. }
+        | ExecuteMsg::MergeCities { .. }
+        | ExecuteMsg::SetJoinFee { .. }
+        | ExecuteMsg::SetCw20JoinFee { .. }
+        | ExecuteMsg::WithdrawCityTreasury { .. }
+        | ExecuteMsg::CreateRegion { .. }
+        | ExecuteMsg::UpdateRegion { .. }
+        | ExecuteMsg::RemoveRegion { .. }
+        | ExecuteMsg::SetCityRegion { .. }
+        | ExecuteMsg::VetoMaintainerClaim { .. }
+        | ExecuteMsg::ProposeMaintainer { .. }
+        | ExecuteMsg::CancelMaintainerProposal { .. }
+        | ExecuteMsg::RenounceMaintainer { .. }
+        | ExecuteMsg::UpdateConfig { .. }
+        | ExecuteMsg::WithdrawProtocolFees { .. }
+        | ExecuteMsg::GrantRole { .. }
+        | ExecuteMsg::RevokeRole { .. }
+        | ExecuteMsg::SetGuardian { .. }
+        | ExecuteMsg::Pause { .. }
+        | ExecuteMsg::Unpause { .. }
+        | ExecuteMsg::SetCouncil { .. }
+    )
+  }
+
+  // Pausable messages are rejected while paused, pause management and council voting stay available
+  pub fn is_pausable(&self) -> bool {
+    !matches!(
+      self,
+      ExecuteMsg::Pause { .. }
+        | ExecuteMsg::Unpause { .. }
+        | ExecuteMsg::CouncilPropose { .. }
+        | ExecuteMsg::CouncilApprove { .. }
+        | ExecuteMsg::CouncilRevokeApproval { .. }
+        | ExecuteMsg::CouncilExecute { .. }
+    )
+  }
+
+  // Action name is the message name in JSON representation, it is used for pausing specific actions
+  pub fn action(&self) -> &'static str {
+    match self {
+      ExecuteMsg::RegisterCity { .. } => "register_city",
+      ExecuteMsg::UpdateCity { .. } => "update_city",
+      ExecuteMsg::SetMayor { .. } => "set_mayor",
+      ExecuteMsg::AppointDeputy { .. } => "appoint_deputy",
+      ExecuteMsg::DismissDeputy { .. } => "dismiss_deputy",
+      ExecuteMsg::KickFromCity { .. } => "kick_from_city",
+      ExecuteMsg::BanFromCity { .. } => "ban_from_city",
+      ExecuteMsg::UnbanFromCity { .. } => "unban_from_city",
+      ExecuteMsg::SetCityProfile { .. } => "set_city_profile",
+      ExecuteMsg::SetCityAgeLimits { .. } => "set_city_age_limits",
+      ExecuteMsg::SetCityCapacity { .. } => "set_city_capacity",
+      ExecuteMsg::SetJoinPolicy { .. } => "set_join_policy",
+      ExecuteMsg::InviteToCity { .. } => "invite_to_city",
+      ExecuteMsg::RevokeInvite { .. } => "revoke_invite",
+      ExecuteMsg::CreateInviteCode { .. } => "create_invite_code",
+      ExecuteMsg::RevokeInviteCode { .. } => "revoke_invite_code",
+      ExecuteMsg::ApproveJoinRequest { .. } => "approve_join_request",
+      ExecuteMsg::RejectJoinRequest { .. } => "reject_join_request",
+      ExecuteMsg::ArchiveCity { .. } => "archive_city",
+      ExecuteMsg::DeleteCity { .. } => "delete_city",
+      ExecuteMsg::MergeCities { .. } => "merge_cities",
+      ExecuteMsg::IndexCityNames { .. } => "index_city_names",
+      ExecuteMsg::SetJoinFee { .. } => "set_join_fee",
+      ExecuteMsg::SetCw20JoinFee { .. } => "set_cw20_join_fee",
+      ExecuteMsg::WithdrawCityTreasury { .. } => "withdraw_city_treasury",
+      ExecuteMsg::CreateRegion { .. } => "create_region",
+      ExecuteMsg::UpdateRegion { .. } => "update_region",
+      ExecuteMsg::RemoveRegion { .. } => "remove_region",
+      ExecuteMsg::SetCityRegion { .. } => "set_city_region",
+      ExecuteMsg::RegisterPerson { .. } => "register_person",
+      ExecuteMsg::UpdatePerson { .. } => "update_person",
+      ExecuteMsg::RegisterInCity { .. } => "register_in_city",
+      ExecuteMsg::UnregisterFromCity { .. } => "unregister_from_city",
+      ExecuteMsg::LeaveWaitlist { .. } => "leave_waitlist",
+      ExecuteMsg::EvictOverage { .. } => "evict_overage",
+      ExecuteMsg::CancelJoinRequest { .. } => "cancel_join_request",
+      ExecuteMsg::ClaimRefund { .. } => "claim_refund",
+      ExecuteMsg::Receive(..) => "receive",
+      ExecuteMsg::BecomeMaintainer { .. } => "become_maintainer",
+      ExecuteMsg::VetoMaintainerClaim { .. } => "veto_maintainer_claim",
+      ExecuteMsg::FinalizeMaintainerClaim { .. } => "finalize_maintainer_claim",
+      ExecuteMsg::ProposeMaintainer { .. } => "propose_maintainer",
+      ExecuteMsg::AcceptMaintainer { .. } => "accept_maintainer",
+      ExecuteMsg::CancelMaintainerProposal { .. } => "cancel_maintainer_proposal",
+      ExecuteMsg::RenounceMaintainer { .. } => "renounce_maintainer",
+      ExecuteMsg::UpdateConfig { .. } => "update_config",
+      ExecuteMsg::WithdrawProtocolFees { .. } => "withdraw_protocol_fees",
+      ExecuteMsg::Nominate { .. } => "nominate",
+      ExecuteMsg::Vote { .. } => "vote",
+      ExecuteMsg::TallyElection { .. } => "tally_election",
+      ExecuteMsg::FinalizeElection { .. } => "finalize_election",
+      ExecuteMsg::GrantRole { .. } => "grant_role",
+      ExecuteMsg::RevokeRole { .. } => "revoke_role",
+      ExecuteMsg::SetGuardian { .. } => "set_guardian",
+      ExecuteMsg::Pause { .. } => "pause",
+      ExecuteMsg::Unpause { .. } => "unpause",
+      ExecuteMsg::SetCouncil { .. } => "set_council",
+      ExecuteMsg::CouncilPropose { .. } => "council_propose",
+      ExecuteMsg::CouncilApprove { .. } => "council_approve",
+      ExecuteMsg::CouncilRevokeApproval { .. } => "council_revoke_approval",
+      ExecuteMsg::CouncilExecute { .. } => "council_execute",
+    }
+  }
+}
+
+// Names of actions able to be paused, it has to be in sync with ExecuteMsg::action and ExecuteMsg::is_pausable
+pub const PAUSABLE_ACTIONS: &[&str] = &[
+  "register_city",
+  "update_city",
+  "set_mayor",
+  "appoint_deputy",
+  "dismiss_deputy",
+  "kick_from_city",
+  "ban_from_city",
+  "unban_from_city",
+  "set_city_profile",
+  "set_city_age_limits",
+  "set_city_capacity",
+  "set_join_policy",
+  "invite_to_city",
+  "revoke_invite",
+  "create_invite_code",
+  "revoke_invite_code",
+  "approve_join_request",
+  "reject_join_request",
+  "archive_city",
+  "delete_city",
+  "merge_cities",
+  "index_city_names",
+  "set_join_fee",
+  "set_cw20_join_fee",
+  "withdraw_city_treasury",
+  "create_region",
+  "update_region",
+  "remove_region",
+  "set_city_region",
+  "register_person",
+  "update_person",
+  "register_in_city",
+  "unregister_from_city",
+  "leave_waitlist",
+  "evict_overage",
+  "cancel_join_request",
+  "claim_refund",
+  "receive",
+  "become_maintainer",
+  "veto_maintainer_claim",
+  "finalize_maintainer_claim",
+  "propose_maintainer",
+  "accept_maintainer",
+  "cancel_maintainer_proposal",
+  "renounce_maintainer",
+  "update_config",
+  "withdraw_protocol_fees",
+  "nominate",
+  "vote",
+  "tally_election",
+  "finalize_election",
+  "grant_role",
+  "revoke_role",
+  "set_guardian",
+  "set_council",
+];
+
+// Receive message is embedded into cw20 Send message, the sent tokens pay city join fee
+// JSON: '{ "message_type": { "virtual_field": "virtual answer", etc. } }'
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+  JoinCity {
+    city_id: u64,
+    invite_code: Option<String>,
+  },
+  RegisterPerson {
+    birthday: Birthday,
+    nickname: Nickname,
+    email: Option<Email>,
+    city_id: u64,
+  },
 }
 
 // Query message
@@ -46,13 +480,145 @@ pub enum ExecuteMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
+  Config {},
+
   LookMaintainer {},
+  LookMaintainerClaim {},
+  LookMaintainerProposal {},
+
+  LookElection {},
+  LookElectionResults {
+    start_after: Option<Addr>,
+    limit: u64,
+  },
+
+  LookCouncil {},
+  LookCouncilProposals {
+    start_after: Option<u64>,
+    limit: u64,
+  },
+
+  LookRoles {
+    address: Addr,
+  },
+  LookRoleHolders {
+    role: Role,
+    start_after: Option<Addr>,
+    limit: u64,
+  },
+
+  LookPauses {},
+
+  LookPerson {
+    person: Addr,
+  },
+  LookCities {
+    start_id: u64,
+    limit: u64,
+  },
+  LookCityByName {
+    name: CityName,
+  },
+  CitiesInArea {
+    min_lat: i32,
+    max_lat: i32,
+    min_lon: i32,
+    max_lon: i32,
+    limit: u64,
+    start_after: Option<Binary>,
+  },
+  NearestCities {
+    latitude: i32,
+    longitude: i32,
+    limit: u64,
+  },
+  LookCityHistory {
+    city_id: u64,
+    start_after: Option<u64>,
+    limit: u64,
+  },
+  LookMayor {
+    city_id: u64,
+  },
+  LookCityDeputies {
+    city_id: u64,
+    start_after: Option<Addr>,
+    limit: u64,
+  },
+  LookAdministeredCities {
+    administrator: Addr,
+    start_after: Option<u64>,
+    limit: u64,
+  },
 
-  LookPerson { person: Addr },
-  LookCities { start_id: u64, limit: u64 },
+  LookRegion {
+    region_id: u64,
+  },
+  LookRegionChildren {
+    region_id: Option<u64>,
+    start_after: Option<u64>,
+    limit: u64,
+  },
+  LookRegionCities {
+    region_id: u64,
+    start_after: Option<u64>,
+    limit: u64,
+  },
+  LookRegionPath {
+    region_id: u64,
+  },
 
-  LookPersonCities { person: Addr, start_id: u64, limit: u64 },
-  LookCityPeople { city: u64, start_id: u64, limit: u64 },
+  LookPersonCities {
+    person: Addr,
+    start_id: u64,
+    limit: u64,
+  },
+  LookCityPeople {
+    city: u64,
+    start_id: u64,
+    limit: u64,
+  },
+  LookWaitlistPosition {
+    city_id: u64,
+    person: Addr,
+  },
+  LookInviteCode {
+    city_id: u64,
+    code_hash: String,
+  },
+  LookCityJoinRequests {
+    city_id: u64,
+    start_after: Option<Addr>,
+    limit: u64,
+  },
+  LookCityBans {
+    city_id: u64,
+    start_after: Option<Addr>,
+    limit: u64,
+  },
+  LookBanStatus {
+    city_id: u64,
+    person: Addr,
+  },
+  LookPersonJoinRequests {
+    person: Addr,
+    start_after: Option<u64>,
+    limit: u64,
+  },
+  LookCw20JoinFees {
+    city_id: u64,
+  },
+  LookCityTreasury {
+    city_id: u64,
+  },
+  LookProtocolTreasury {},
+  LookEscrowedJoinFee {
+    city_id: u64,
+    person: Addr,
+  },
+  LookRefunds {
+    person: Addr,
+  },
 }
 
 // Response message
@@ -60,11 +626,133 @@ pub enum QueryMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ResponseMsg {
-  LookMaintainer { maintainer: Addr },
+  Config {
+    config: ConfigResponse,
+  },
+
+  LookMaintainer {
+    maintainer: Option<Addr>,
+    frozen: bool,
+  },
+  LookMaintainerClaim {
+    claim: Option<MaintainerClaimResponse>,
+  },
+  LookMaintainerProposal {
+    proposal: Option<MaintainerProposalResponse>,
+  },
+
+  LookElection {
+    election: Option<ElectionResponse>,
+  },
+  LookElectionResults {
+    candidates: Vec<CandidateResponse>,
+  },
 
-  LookPerson { person: PersonResponse },
-  LookCities { cities: Vec<CityResponse> },
+  LookCouncil {
+    council: Option<CouncilResponse>,
+  },
+  LookCouncilProposals {
+    proposals: Vec<CouncilProposalResponse>,
+  },
+
+  LookRoles {
+    roles: Vec<Role>,
+  },
+  LookRoleHolders {
+    holders: Vec<Addr>,
+  },
+
+  LookPauses {
+    reason: Option<String>,
+    actions: Vec<PausedAction>,
+  },
 
-  LookPersonCities { cities: Vec<CityResponse> },
-  LookCityPeople { people: Vec<PersonResponse> },
+  LookPerson {
+    person: PersonResponse,
+  },
+  LookCities {
+    cities: Vec<CityResponse>,
+  },
+  LookCityByName {
+    city: CityResponse,
+  },
+  CitiesInArea {
+    cities: Vec<CityResponse>,
+    next_key: Option<Binary>,
+  },
+  NearestCities {
+    cities: Vec<CityResponse>,
+    is_complete: bool,
+  },
+  LookCityHistory {
+    changes: Vec<CityChangeResponse>,
+  },
+  LookMayor {
+    mayor: Option<Addr>,
+  },
+  LookCityDeputies {
+    deputies: Vec<Addr>,
+  },
+  LookAdministeredCities {
+    cities: Vec<AdministeredCityResponse>,
+  },
+
+  LookRegion {
+    region: RegionResponse,
+  },
+  LookRegionChildren {
+    regions: Vec<RegionResponse>,
+  },
+  LookRegionCities {
+    cities: Vec<CityResponse>,
+  },
+  LookRegionPath {
+    path: Vec<RegionResponse>,
+  },
+
+  LookPersonCities {
+    cities: Vec<CityResponse>,
+  },
+  LookCityPeople {
+    people: Vec<PersonResponse>,
+  },
+  LookWaitlistPosition {
+    position: Option<u64>,
+    length: u64,
+  },
+  LookInviteCode {
+    invite_code: Option<InviteCodeResponse>,
+  },
+  LookCityJoinRequests {
+    requests: Vec<JoinRequestResponse>,
+  },
+  LookCityBans {
+    bans: Vec<CityBanResponse>,
+  },
+  LookBanStatus {
+    banned: bool,
+    until: Option<Expiration>,
+  },
+  LookPersonJoinRequests {
+    requests: Vec<JoinRequestResponse>,
+  },
+  LookCw20JoinFees {
+    join_fees: Vec<Cw20Coin>,
+  },
+  LookCityTreasury {
+    balances: Vec<Coin>,
+    cw20_balances: Vec<Cw20Coin>,
+  },
+  LookProtocolTreasury {
+    balances: Vec<Coin>,
+    cw20_balances: Vec<Cw20Coin>,
+  },
+  LookEscrowedJoinFee {
+    join_fee: Option<Coin>,
+    cw20_join_fee: Option<Cw20Coin>,
+  },
+  LookRefunds {
+    refunds: Vec<Coin>,
+    cw20_refunds: Vec<Cw20Coin>,
+  },
 }
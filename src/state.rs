@@ -1,17 +1,36 @@
 // The file contains storage structs and helper functions for interactions with stored data
 
 // Import section contains all needed imports
-use cosmwasm_std::{CanonicalAddr, Storage};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{BlockInfo, CanonicalAddr, Coin, Empty, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Item, Map, Prefix, PrimaryKey};
+use serde::{de::DeserializeOwned, Serialize};
 
 // The crate imports are responsible for import from anothe project file
 use crate::{
   error::{wrap_not_found, ContractError},
-  utils::{Birthday, City, Config, Person},
+  utils::{
+    geohash_area_cells, Birthday, City, CityBan, CityChange, CityOffice, CityProfile, CityStatus, Config, Coordinates,
+    Council, CouncilProposal, Election, InviteCode, JoinPolicy, JoinRequest, LegacyConfig, MaintainerClaim,
+    MaintainerProposal, Person, Region, RegionKind, RegionName, Role, Vote, BASIS_POINTS, GEOHASH_PRECISION,
+  },
 };
 
 // Config instance that is stored by specific key
 const CONFIG_INSTANCE: Item<Config> = Item::new("config_key");
+// Legacy config instance is the same storage key read with layout of contract version 1.0.0
+const LEGACY_CONFIG_INSTANCE: Item<LegacyConfig> = Item::new("config_key");
+// Maintainer proposal instance that is stored next to config, it exists only while handover is pending
+const MAINTAINER_PROPOSAL_INSTANCE: Item<MaintainerProposal> = Item::new("maintainer_proposal_key");
+// Maintainer claim instance that is stored next to config, it exists only while takeover is pending
+const MAINTAINER_CLAIM_INSTANCE: Item<MaintainerClaim> = Item::new("maintainer_claim_key");
+// Pause reason instance that is stored by specific key, it exists only while the whole execute entrypoint is paused
+const PAUSE_REASON_INSTANCE: Item<String> = Item::new("pause_reason_key");
+// Council instance that is stored by specific key, it exists only in council mode
+const COUNCIL_INSTANCE: Item<Council> = Item::new("council_key");
+// Election instance that is stored by specific key, it keeps the latest election
+const ELECTION_INSTANCE: Item<Election> = Item::new("election_key");
+// City names index cursor instance keeps id of the next city to index, it exists only while names of migrated cities are indexed
+const CITY_NAMES_INDEX_CURSOR_INSTANCE: Item<u64> = Item::new("city_names_index_cursor_key");
 
 // Mapping instances that are stored by specific keys
 // CanonicalAddr could be represented as &[u8] which is valid value for mpping key
@@ -19,6 +38,97 @@ const CONFIG_INSTANCE: Item<Config> = Item::new("config_key");
 const PERSON_BY_ADDRESS: Map<&[u8], Person> = Map::new("person_by_address");
 // CityByID is mapping from city index to City object
 const CITY_BY_ID: Map<u64, City> = Map::new("city_by_id");
+// CityIDByName is mapping from normalized city name to city index, it keeps city names unique
+const CITY_ID_BY_NAME: Map<&str, u64> = Map::new("city_id_by_name");
+// CityIDByGeohash is mapping from geohash of city coordinates followed by city index to city index
+// Keys are raw bytes, so cities within geohash cell are ranged by the cell prefix
+const CITY_ID_BY_GEOHASH: Map<&[u8], u64> = Map::new("city_id_by_geohash");
+// Maximal number of geohash cells ranged by area query, larger areas are covered by less precise cells
+const MAX_AREA_CELLS: u64 = 16;
+// Lowest geohash precision the nearest cities neighbourhood is widened to, its cells are about 600 km wide
+const MIN_NEAREST_PRECISION: usize = 2;
+// Maximal number of cities scanned within single nearest cities neighbourhood
+const MAX_NEAREST_SCAN: usize = 256;
+// Maximal number of cities scanned by single cities in area query
+const MAX_AREA_SCAN: usize = 256;
+// CityChangeByCityIDAndChangeID is mapping from city index and change index to CityChange object
+const CITY_CHANGE_BY_CITY_ID_AND_CHANGE_ID: Map<(u64, u64), CityChange> =
+  Map::new("city_change_by_city_id_and_change_id");
+// Mapping between city administrator address, city id and office held, is used to list cities administered by address
+const CITY_OFFICE_BY_ADDRESS_AND_CITY_ID: Map<(&[u8], u64), CityOffice> =
+  Map::new("city_office_by_address_and_city_id");
+// Mapping between city id and deputy address, is used to list city deputies
+const EMPTY_BY_CITY_ID_AND_DEPUTY: Map<(u64, &[u8]), Empty> = Map::new("empty_by_city_id_and_deputy");
+
+// Mapping between city id, person address and pending join request
+const JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS: Map<(u64, &[u8]), JoinRequest> =
+  Map::new("join_request_by_city_id_and_person_address");
+// Mapping between person address and city id of pending join request, is used to list join requests of person
+const EMPTY_BY_PERSON_ADDRESS_AND_JOIN_REQUEST_CITY_ID: Map<(&[u8], u64), Empty> =
+  Map::new("empty_by_person_address_and_join_request_city_id");
+// Mapping between city id and invited person address
+const EMPTY_BY_CITY_ID_AND_INVITEE: Map<(u64, &[u8]), Empty> = Map::new("empty_by_city_id_and_invitee");
+// Mapping between city id, banned person address and the ban, is used to list city bans
+const CITY_BAN_BY_CITY_ID_AND_PERSON_ADDRESS: Map<(u64, &[u8]), CityBan> =
+  Map::new("city_ban_by_city_id_and_person_address");
+// Mapping between city id, sha256 hash of invite code and invite code data
+const INVITE_CODE_BY_CITY_ID_AND_CODE_HASH: Map<(u64, &[u8]), InviteCode> =
+  Map::new("invite_code_by_city_id_and_code_hash");
+// Mapping between city id, waitlist ticket and waiting person address, is used to admit people in joining order
+const PERSON_ADDRESS_BY_CITY_ID_AND_WAITLIST_TICKET: Map<(u64, u64), CanonicalAddr> =
+  Map::new("person_address_by_city_id_and_waitlist_ticket");
+// Mapping between waiting person address, city id and waitlist ticket
+const WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID: Map<(&[u8], u64), u64> =
+  Map::new("waitlist_ticket_by_person_address_and_city_id");
+
+// RegionByID is mapping from region index to Region object
+const REGION_BY_ID: Map<u64, Region> = Map::new("region_by_id");
+// Mapping between country id and Empty object, is used to list countries
+const EMPTY_BY_COUNTRY_ID: Map<u64, Empty> = Map::new("empty_by_country_id");
+// Mapping between country id and child region id, is used to list regions of country
+const EMPTY_BY_PARENT_ID_AND_REGION_ID: Map<(u64, u64), Empty> = Map::new("empty_by_parent_id_and_region_id");
+// Mapping between region id and city id, is used to list cities of region
+const EMPTY_BY_REGION_ID_AND_CITY_ID: Map<(u64, u64), Empty> = Map::new("empty_by_region_id_and_city_id");
+
+// Mapping between city id, denom and city treasury balance
+const BALANCE_BY_CITY_ID_AND_DENOM: Map<(u64, &str), Uint128> = Map::new("balance_by_city_id_and_denom");
+// Mapping between denom and protocol treasury balance, it is withdrawn by maintainer
+const PROTOCOL_BALANCE_BY_DENOM: Map<&str, Uint128> = Map::new("protocol_balance_by_denom");
+// Mapping between city id, person address and join fee kept while the person is waiting for approval or free slot
+const JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS: Map<(u64, &[u8]), Coin> =
+  Map::new("join_fee_by_city_id_and_person_address");
+// Mapping between person address, denom and refund balance of join fees the person paid without joining the city
+const REFUND_BY_PERSON_ADDRESS_AND_DENOM: Map<(&[u8], &str), Uint128> = Map::new("refund_by_person_address_and_denom");
+
+// Mapping between city id, accepted cw20 token address and join fee amount in the token
+const CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN: Map<(u64, &[u8]), Uint128> = Map::new("cw20_join_fee_by_city_id_and_token");
+// Mapping between city id, cw20 token address and city treasury balance
+const CW20_BALANCE_BY_CITY_ID_AND_TOKEN: Map<(u64, &[u8]), Uint128> = Map::new("cw20_balance_by_city_id_and_token");
+// Mapping between cw20 token address and protocol treasury balance
+const CW20_PROTOCOL_BALANCE_BY_TOKEN: Map<&[u8], Uint128> = Map::new("cw20_protocol_balance_by_token");
+// Mapping between city id, person address, cw20 token address and join fee amount kept for the person
+const CW20_JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS: Map<(u64, &[u8]), (CanonicalAddr, Uint128)> =
+  Map::new("cw20_join_fee_by_city_id_and_person_address");
+// Mapping between person address, cw20 token address and refund balance
+const CW20_REFUND_BY_PERSON_ADDRESS_AND_TOKEN: Map<(&[u8], &[u8]), Uint128> =
+  Map::new("cw20_refund_by_person_address_and_token");
+
+// PauseReasonByAction is mapping from paused action name to pause reason
+const PAUSE_REASON_BY_ACTION: Map<&str, String> = Map::new("pause_reason_by_action");
+// CouncilProposalByID is mapping from proposal index to pending CouncilProposal object
+const COUNCIL_PROPOSAL_BY_ID: Map<u64, CouncilProposal> = Map::new("council_proposal_by_id");
+
+// Mapping instances responsible for granted roles that are stored by specific keys
+// RoleByAddressAndRoleKey is mapping from address and role key to Role object
+const ROLE_BY_ADDRESS_AND_ROLE_KEY: Map<(&[u8], &str), Role> = Map::new("role_by_address_and_role_key");
+// EmptyByRoleKeyAndAddress is mapping from role key and address to Empty object, it is used for listing role holders
+const EMPTY_BY_ROLE_KEY_AND_ADDRESS: Map<(&str, &[u8]), Empty> = Map::new("empty_by_role_key_and_address");
+
+// Mapping instances responsible for elections that are stored by specific keys
+// VotesByElectionIDAndCandidate is mapping from election index and candidate address to total weight of votes
+const VOTES_BY_ELECTION_ID_AND_CANDIDATE: Map<(u64, &[u8]), u64> = Map::new("votes_by_election_id_and_candidate");
+// VoteByElectionIDAndVoter is mapping from election index and voter address to Vote object
+const VOTE_BY_ELECTION_ID_AND_VOTER: Map<(u64, &[u8]), Vote> = Map::new("vote_by_election_id_and_voter");
 
 // Mapping instances responsible for link between person and city that are stored by specific keys
 // PersonAddressByCityIDAndPersonInCityID is mapping from city index and person in the city index to person address
@@ -32,88 +142,1798 @@ const CITY_ID_BY_PERSON_ADDRESS_AND_CITY_IN_PERSON_ID: Map<(&[u8], u64), u64> =
 const PERSON_IN_CITY_ID_AND_CITY_IN_PERSON_ID_BY_PERSON_ADDRESS_AND_CITY_ID: Map<(&[u8], u64), (u64, u64)> =
   Map::new("person_in_city_id_and_city_in_person_id_by_person_address_and_city_id");
 
-// Helper functions for loading person/city data
-pub fn get_person(store: &dyn Storage, addr: CanonicalAddr) -> Result<Person, ContractError> {
-  Ok(wrap_not_found(PERSON_BY_ADDRESS.load(store, &addr))?)
+// Helper functions for loading person/city data
+pub fn get_person(store: &dyn Storage, addr: CanonicalAddr) -> Result<Person, ContractError> {
+  wrap_not_found(PERSON_BY_ADDRESS.load(store, &addr))
+}
+pub fn get_city(store: &dyn Storage, city_id: u64) -> Result<City, ContractError> {
+  wrap_not_found(CITY_BY_ID.load(store, city_id))
+}
+
+// Helper functions for accessing person/city from city/person data
+pub fn get_city_id_by_person(store: &dyn Storage, addr: CanonicalAddr, index: u64) -> Result<u64, ContractError> {
+  Ok(CITY_ID_BY_PERSON_ADDRESS_AND_CITY_IN_PERSON_ID.load(store, (&addr, index))?)
+}
+pub fn get_person_address_by_city(
+  store: &dyn Storage,
+  city_id: u64,
+  index: u64,
+) -> Result<CanonicalAddr, ContractError> {
+  Ok(PERSON_ADDRESS_BY_CITY_ID_AND_PERSON_IN_CITY_ID.load(store, (city_id, index))?)
+}
+
+// Helper functions for loading/saving config data
+pub fn get_storage(store: &dyn Storage) -> Result<Config, ContractError> {
+  Ok(CONFIG_INSTANCE.load(store)?)
+}
+pub fn set_storage(store: &mut dyn Storage, config: &Config) -> Result<(), ContractError> {
+  CONFIG_INSTANCE.save(store, config)?;
+
+  Ok(())
+}
+
+// Helper function for loading config stored by contract version 1.0.0
+pub fn get_legacy_config(store: &dyn Storage) -> Result<LegacyConfig, ContractError> {
+  Ok(LEGACY_CONFIG_INSTANCE.load(store)?)
+}
+
+// Helper functions for loading/saving/removing pending maintainer proposal
+pub fn get_maintainer_proposal(store: &dyn Storage) -> Result<MaintainerProposal, ContractError> {
+  wrap_not_found(MAINTAINER_PROPOSAL_INSTANCE.load(store))
+}
+pub fn may_get_maintainer_proposal(store: &dyn Storage) -> Result<Option<MaintainerProposal>, ContractError> {
+  Ok(MAINTAINER_PROPOSAL_INSTANCE.may_load(store)?)
+}
+pub fn set_maintainer_proposal(store: &mut dyn Storage, proposal: &MaintainerProposal) -> Result<(), ContractError> {
+  MAINTAINER_PROPOSAL_INSTANCE.save(store, proposal)?;
+
+  Ok(())
+}
+pub fn remove_maintainer_proposal(store: &mut dyn Storage) {
+  MAINTAINER_PROPOSAL_INSTANCE.remove(store);
+}
+
+// Helper functions for loading/saving/removing pending maintainer claim
+pub fn get_maintainer_claim(store: &dyn Storage) -> Result<MaintainerClaim, ContractError> {
+  wrap_not_found(MAINTAINER_CLAIM_INSTANCE.load(store))
+}
+pub fn may_get_maintainer_claim(store: &dyn Storage) -> Result<Option<MaintainerClaim>, ContractError> {
+  Ok(MAINTAINER_CLAIM_INSTANCE.may_load(store)?)
+}
+pub fn set_maintainer_claim(store: &mut dyn Storage, claim: &MaintainerClaim) -> Result<(), ContractError> {
+  MAINTAINER_CLAIM_INSTANCE.save(store, claim)?;
+
+  Ok(())
+}
+pub fn remove_maintainer_claim(store: &mut dyn Storage) {
+  MAINTAINER_CLAIM_INSTANCE.remove(store);
+}
+
+// Helper functions for loading/saving/removing pause reason of the whole execute entrypoint
+pub fn may_get_pause_reason(store: &dyn Storage) -> Result<Option<String>, ContractError> {
+  Ok(PAUSE_REASON_INSTANCE.may_load(store)?)
+}
+pub fn set_pause_reason(store: &mut dyn Storage, reason: &str) -> Result<(), ContractError> {
+  PAUSE_REASON_INSTANCE.save(store, &reason.to_string())?;
+
+  Ok(())
+}
+pub fn remove_pause_reason(store: &mut dyn Storage) {
+  PAUSE_REASON_INSTANCE.remove(store);
+}
+
+// Helper functions for loading/saving/removing pause reason of specific action
+pub fn may_get_action_pause_reason(store: &dyn Storage, action: &str) -> Result<Option<String>, ContractError> {
+  Ok(PAUSE_REASON_BY_ACTION.may_load(store, action)?)
+}
+pub fn set_action_pause_reason(store: &mut dyn Storage, action: &str, reason: &str) -> Result<(), ContractError> {
+  PAUSE_REASON_BY_ACTION.save(store, action, &reason.to_string())?;
+
+  Ok(())
+}
+pub fn remove_action_pause_reason(store: &mut dyn Storage, action: &str) {
+  PAUSE_REASON_BY_ACTION.remove(store, action);
+}
+pub fn clear_action_pause_reasons(store: &mut dyn Storage) -> Result<(), ContractError> {
+  let actions = PAUSE_REASON_BY_ACTION
+    .keys(store, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<String>>>()?;
+  for action in actions {
+    PAUSE_REASON_BY_ACTION.remove(store, &action);
+  }
+
+  Ok(())
+}
+pub fn get_action_pause_reasons(store: &dyn Storage) -> Result<Vec<(String, String)>, ContractError> {
+  Ok(
+    PAUSE_REASON_BY_ACTION
+      .range(store, None, None, Order::Ascending)
+      .collect::<StdResult<Vec<(String, String)>>>()?,
+  )
+}
+
+// Helper functions for loading/saving/removing council
+pub fn may_get_council(store: &dyn Storage) -> Result<Option<Council>, ContractError> {
+  Ok(COUNCIL_INSTANCE.may_load(store)?)
+}
+pub fn set_council(store: &mut dyn Storage, council: &Council) -> Result<(), ContractError> {
+  COUNCIL_INSTANCE.save(store, council)?;
+
+  Ok(())
+}
+pub fn remove_council(store: &mut dyn Storage) {
+  COUNCIL_INSTANCE.remove(store);
+}
+
+// Helper functions for loading/saving/removing council proposals
+pub fn get_council_proposal(store: &dyn Storage, proposal_id: u64) -> Result<CouncilProposal, ContractError> {
+  wrap_not_found(COUNCIL_PROPOSAL_BY_ID.load(store, proposal_id))
+}
+pub fn set_council_proposal(
+  store: &mut dyn Storage,
+  proposal_id: u64,
+  proposal: &CouncilProposal,
+) -> Result<(), ContractError> {
+  COUNCIL_PROPOSAL_BY_ID.save(store, proposal_id, proposal)?;
+
+  Ok(())
+}
+pub fn remove_council_proposal(store: &mut dyn Storage, proposal_id: u64) {
+  COUNCIL_PROPOSAL_BY_ID.remove(store, proposal_id);
+}
+pub fn get_council_proposals(
+  store: &dyn Storage,
+  start_after: Option<u64>,
+) -> impl Iterator<Item = Result<(u64, CouncilProposal), ContractError>> + '_ {
+  COUNCIL_PROPOSAL_BY_ID
+    .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+    .map(|item| Ok(item?))
+}
+
+// Helper function for adding new council proposal
+pub fn create_council_proposal(store: &mut dyn Storage, proposal: &CouncilProposal) -> Result<u64, ContractError> {
+  let mut config = get_storage(store)?;
+  let proposal_id = config.council_proposals_count;
+
+  COUNCIL_PROPOSAL_BY_ID.save(store, proposal_id, proposal)?;
+
+  config.council_proposals_count += 1;
+  set_storage(store, &config)?;
+
+  Ok(proposal_id)
+}
+
+// Helper functions for loading/saving election
+pub fn get_election(store: &dyn Storage) -> Result<Election, ContractError> {
+  wrap_not_found(ELECTION_INSTANCE.load(store))
+}
+pub fn may_get_election(store: &dyn Storage) -> Result<Option<Election>, ContractError> {
+  Ok(ELECTION_INSTANCE.may_load(store)?)
+}
+pub fn set_election(store: &mut dyn Storage, election: &Election) -> Result<(), ContractError> {
+  ELECTION_INSTANCE.save(store, election)?;
+
+  Ok(())
+}
+
+// Helper functions for loading/saving candidate votes and listing candidates
+pub fn may_get_candidate_votes(
+  store: &dyn Storage,
+  election_id: u64,
+  candidate: &CanonicalAddr,
+) -> Result<Option<u64>, ContractError> {
+  Ok(VOTES_BY_ELECTION_ID_AND_CANDIDATE.may_load(store, (election_id, candidate))?)
+}
+pub fn set_candidate_votes(
+  store: &mut dyn Storage,
+  election_id: u64,
+  candidate: &CanonicalAddr,
+  votes: u64,
+) -> Result<(), ContractError> {
+  VOTES_BY_ELECTION_ID_AND_CANDIDATE.save(store, (election_id, candidate), &votes)?;
+
+  Ok(())
+}
+pub fn get_candidates(
+  store: &dyn Storage,
+  election_id: u64,
+  start_after: Option<CanonicalAddr>,
+) -> impl Iterator<Item = Result<(CanonicalAddr, u64), ContractError>> + '_ {
+  VOTES_BY_ELECTION_ID_AND_CANDIDATE
+    .prefix(election_id)
+    .range(
+      store,
+      start_after.map(|addr| Bound::ExclusiveRaw(addr.to_vec())),
+      None,
+      Order::Ascending,
+    )
+    .map(|item| {
+      item
+        .map(|(candidate, votes)| (CanonicalAddr::from(candidate), votes))
+        .map_err(ContractError::from)
+    })
+}
+
+// Helper functions for loading/saving votes
+pub fn may_get_vote(
+  store: &dyn Storage,
+  election_id: u64,
+  voter: &CanonicalAddr,
+) -> Result<Option<Vote>, ContractError> {
+  Ok(VOTE_BY_ELECTION_ID_AND_VOTER.may_load(store, (election_id, voter))?)
+}
+pub fn set_vote(
+  store: &mut dyn Storage,
+  election_id: u64,
+  voter: &CanonicalAddr,
+  vote: &Vote,
+) -> Result<(), ContractError> {
+  VOTE_BY_ELECTION_ID_AND_VOTER.save(store, (election_id, voter), vote)?;
+
+  Ok(())
+}
+
+// Helper functions for checking/granting/revoking roles
+pub fn has_role(store: &dyn Storage, addr: &CanonicalAddr, role: Role) -> bool {
+  ROLE_BY_ADDRESS_AND_ROLE_KEY.has(store, (addr, role.key()))
+}
+pub fn grant_role(store: &mut dyn Storage, addr: &CanonicalAddr, role: Role) -> Result<(), ContractError> {
+  ROLE_BY_ADDRESS_AND_ROLE_KEY.save(store, (addr, role.key()), &role)?;
+  EMPTY_BY_ROLE_KEY_AND_ADDRESS.save(store, (role.key(), addr), &Empty {})?;
+
+  Ok(())
+}
+pub fn revoke_role(store: &mut dyn Storage, addr: &CanonicalAddr, role: Role) {
+  ROLE_BY_ADDRESS_AND_ROLE_KEY.remove(store, (addr, role.key()));
+  EMPTY_BY_ROLE_KEY_AND_ADDRESS.remove(store, (role.key(), addr));
+}
+
+// Helper functions for listing roles of address and holders of role
+pub fn get_roles_by_address(store: &dyn Storage, addr: &CanonicalAddr) -> Result<Vec<Role>, ContractError> {
+  Ok(
+    ROLE_BY_ADDRESS_AND_ROLE_KEY
+      .prefix(addr)
+      .range(store, None, None, Order::Ascending)
+      .map(|item| item.map(|(_, role)| role))
+      .collect::<StdResult<Vec<Role>>>()?,
+  )
+}
+pub fn get_role_holders(
+  store: &dyn Storage,
+  role: Role,
+  start_after: Option<CanonicalAddr>,
+  limit: u64,
+) -> Result<Vec<CanonicalAddr>, ContractError> {
+  Ok(
+    EMPTY_BY_ROLE_KEY_AND_ADDRESS
+      .prefix(role.key())
+      .keys(
+        store,
+        start_after.map(|addr| Bound::ExclusiveRaw(addr.to_vec())),
+        None,
+        Order::Ascending,
+      )
+      .take(limit as usize)
+      .map(|item| item.map(CanonicalAddr::from))
+      .collect::<StdResult<Vec<CanonicalAddr>>>()?,
+  )
+}
+
+// Helper functions for updating counters
+fn increment_city_counter(store: &mut dyn Storage) -> Result<(), ContractError> {
+  let mut config = get_storage(store)?;
+  config.cities_count += 1;
+  set_storage(store, &config)?;
+
+  Ok(())
+}
+fn crement_person_and_city_counters(
+  store: &mut dyn Storage,
+  addr: CanonicalAddr,
+  city_id: u64,
+  increment: bool,
+) -> Result<(), ContractError> {
+  // Load
+  let mut person = get_person(store, addr.clone())?;
+  let mut city = get_city(store, city_id)?;
+
+  // Change
+  if increment {
+    person.cities_count += 1;
+    city.members_count += 1;
+  } else {
+    person.cities_count -= 1;
+    city.members_count -= 1;
+  }
+
+  // Save
+  PERSON_BY_ADDRESS.save(store, &addr, &person)?;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  // Regions containing the city are populated with the city
+  if let Some(region_id) = city.region_id {
+    change_region_population(store, region_id, 1, increment)?;
+  }
+
+  Ok(())
+}
+
+// Helper function for normalizing city name, names differing in case and whitespace only are the same
+fn normalize_city_name(city_name: &str) -> String {
+  city_name
+    .split_whitespace()
+    .collect::<Vec<&str>>()
+    .join(" ")
+    .to_lowercase()
+}
+
+// Helper function for checking names of migrated cities are indexed, name lookups are not reliable until then
+fn check_city_names_indexed(store: &dyn Storage) -> Result<(), ContractError> {
+  if CITY_NAMES_INDEX_CURSOR_INSTANCE.may_load(store)?.is_some() {
+    return Err(ContractError::CityNamesNotIndexed {});
+  }
+
+  Ok(())
+}
+
+// Helper function for reserving city name, fails if the name is taken by another city
+fn reserve_city_name(store: &mut dyn Storage, city_id: u64, city_name: &str) -> Result<(), ContractError> {
+  check_city_names_indexed(store)?;
+  let key = normalize_city_name(city_name);
+  match CITY_ID_BY_NAME.may_load(store, &key)? {
+    Some(id) if id != city_id => Err(ContractError::CityNameTaken {
+      city_name: city_name.to_string(),
+    }),
+    _ => Ok(CITY_ID_BY_NAME.save(store, &key, &city_id)?),
+  }
+}
+
+// Helper function for releasing city name reserved by the city
+fn release_city_name(store: &mut dyn Storage, city_id: u64, city_name: &str) -> Result<(), ContractError> {
+  let key = normalize_city_name(city_name);
+  if CITY_ID_BY_NAME.may_load(store, &key)? == Some(city_id) {
+    CITY_ID_BY_NAME.remove(store, &key);
+  }
+
+  Ok(())
+}
+
+// Helper function for finding city by name
+pub fn may_get_city_id_by_name(store: &dyn Storage, city_name: &str) -> Result<Option<u64>, ContractError> {
+  check_city_names_indexed(store)?;
+  Ok(CITY_ID_BY_NAME.may_load(store, &normalize_city_name(city_name))?)
+}
+
+// Helper function for starting indexing of existing city names, it is continued by batches
+pub fn start_city_names_indexing(store: &mut dyn Storage) -> Result<(), ContractError> {
+  Ok(CITY_NAMES_INDEX_CURSOR_INSTANCE.save(store, &0)?)
+}
+
+// Helper function for indexing batch of existing city names, the first city keeps duplicated name
+// Returns whether all names are indexed
+pub fn index_city_names_batch(store: &mut dyn Storage, batch_size: u64) -> Result<bool, ContractError> {
+  let start_id = match CITY_NAMES_INDEX_CURSOR_INSTANCE.may_load(store)? {
+    Some(start_id) => start_id,
+    None => return Ok(true),
+  };
+
+  // Load one city more than the batch to find where the next batch starts
+  let cities = get_cities(store, start_id)
+    .take(batch_size as usize + 1)
+    .collect::<Result<Vec<(u64, City)>, ContractError>>()?;
+  let next_id = cities.get(batch_size as usize).map(|(city_id, _)| *city_id);
+  for (city_id, city) in cities.into_iter().take(batch_size as usize) {
+    let key = normalize_city_name(&city.city_name);
+    if !CITY_ID_BY_NAME.has(store, &key) {
+      CITY_ID_BY_NAME.save(store, &key, &city_id)?;
+    }
+  }
+
+  match next_id {
+    Some(next_id) => {
+      CITY_NAMES_INDEX_CURSOR_INSTANCE.save(store, &next_id)?;
+      Ok(false)
+    }
+    None => {
+      CITY_NAMES_INDEX_CURSOR_INSTANCE.remove(store);
+      Ok(true)
+    }
+  }
+}
+
+// Helper function for building key of coordinates index
+fn geohash_key(coordinates: &Coordinates, city_id: u64) -> Vec<u8> {
+  let mut key = coordinates.geohash(GEOHASH_PRECISION).into_bytes();
+  key.extend_from_slice(&city_id.to_be_bytes());
+  key
+}
+
+// Helper function for listing cities within geohash cells ordered by key, starting after the key
+fn get_cities_in_cells<'a>(
+  store: &'a dyn Storage,
+  cells: &'a [String],
+  start_after: Option<Vec<u8>>,
+) -> impl Iterator<Item = Result<(Vec<u8>, u64, City), ContractError>> + 'a {
+  cells.iter().flat_map(move |cell| {
+    // Empty cell is the whole world
+    let start = cell.as_bytes().to_vec();
+    let mut end = start.clone();
+    if let Some(last) = end.last_mut() {
+      *last += 1;
+    }
+
+    let min = match &start_after {
+      Some(after) if *after >= start => Some(Bound::ExclusiveRaw(after.clone())),
+      _ => Some(Bound::InclusiveRaw(start)),
+    };
+    let max = (!end.is_empty()).then_some(Bound::ExclusiveRaw(end));
+
+    CITY_ID_BY_GEOHASH
+      .range_raw(store, min, max, Order::Ascending)
+      .map(move |item| {
+        let (key, city_id) = item?;
+        Ok((key, city_id, get_city(store, city_id)?))
+      })
+  })
+}
+
+// Cities page with key of the last scanned coordinates index entry to continue from
+type CitiesPage = (Vec<(u64, City)>, Option<Vec<u8>>);
+
+// Helper function for listing cities within area ordered by geohash, only cells covering the area are ranged
+// Scan is limited since low precision cells may hold many cities out of the area, key of the last scanned city is
+// returned while the cells are not scanned completely
+pub fn get_cities_in_area(
+  store: &dyn Storage,
+  min: &Coordinates,
+  max: &Coordinates,
+  start_after: Option<Vec<u8>>,
+  limit: u64,
+) -> Result<CitiesPage, ContractError> {
+  let cells = geohash_area_cells(min, max, MAX_AREA_CELLS);
+  let mut cities = vec![];
+  let mut last_key = start_after.clone();
+  for (scanned, item) in get_cities_in_cells(store, &cells, start_after).enumerate() {
+    if cities.len() as u64 == limit || scanned == MAX_AREA_SCAN {
+      return Ok((cities, last_key));
+    }
+
+    let (key, city_id, city) = item?;
+    if city
+      .profile
+      .coordinates
+      .map_or(false, |coordinates| coordinates.is_within(min, max))
+    {
+      cities.push((city_id, city));
+    }
+    last_key = Some(key);
+  }
+
+  Ok((cities, None))
+}
+
+// Helper function for finding nearest cities to the point, ordered by distance
+// Neighbourhood of the point is widened until it surely contains the nearest cities
+// Widening stops at the lowest precision or at too dense neighbourhood, then the cities found so far are returned
+// Returns whether the cities are surely the nearest ones
+pub fn get_nearest_cities(
+  store: &dyn Storage,
+  point: &Coordinates,
+  limit: u64,
+) -> Result<(Vec<(u64, City)>, bool), ContractError> {
+  let mut nearest = vec![];
+  let mut is_complete = false;
+  for precision in (MIN_NEAREST_PRECISION..=GEOHASH_PRECISION).rev() {
+    let cells = point.neighbour_cells(precision);
+    let scanned = get_cities_in_cells(store, &cells, None)
+      .take(MAX_NEAREST_SCAN + 1)
+      .map(|item| item.map(|(_, city_id, city)| (city_id, city)))
+      .collect::<Result<Vec<(u64, City)>, ContractError>>()?;
+    if scanned.len() > MAX_NEAREST_SCAN {
+      break;
+    }
+
+    let mut cities = scanned
+      .into_iter()
+      .map(|(city_id, city)| {
+        let distance = city
+          .profile
+          .coordinates
+          .map_or(i64::MAX, |coordinates| point.distance_squared(&coordinates));
+        (distance, city_id, city)
+      })
+      .collect::<Vec<(i64, u64, City)>>();
+    cities.sort_by_key(|(distance, city_id, _)| (*distance, *city_id));
+    cities.truncate(limit as usize);
+
+    // Cities farther than neighbourhood radius may be closer than found ones
+    is_complete = match cities.last() {
+      Some((distance, _, _)) if cities.len() as u64 == limit => {
+        let radius = point.neighbourhood_radius(precision);
+        *distance <= radius * radius
+      }
+      _ => false,
+    };
+    nearest = cities;
+    if is_complete {
+      break;
+    }
+  }
+
+  Ok((
+    nearest.into_iter().map(|(_, city_id, city)| (city_id, city)).collect(),
+    is_complete,
+  ))
+}
+
+// Helper function for adding new city
+pub fn create_city(store: &mut dyn Storage, city_name: String, power_level: u8) -> Result<(), ContractError> {
+  let config = get_storage(store)?.cities_count;
+  reserve_city_name(store, config, &city_name)?;
+  CITY_BY_ID.save(
+    store,
+    config,
+    &City {
+      city_name,
+      power_level,
+      members_count: 0,
+      mayor: None,
+      deputies_count: 0,
+      changes_count: 0,
+      status: CityStatus::Active,
+      join_policy: JoinPolicy::Open,
+      max_members: None,
+      waitlist_count: 0,
+      waitlist_tickets_count: 0,
+      min_age: None,
+      max_age: None,
+      join_fee: None,
+      region_id: None,
+      profile: CityProfile::default(),
+      merged_into: None,
+      merges_in_progress: 0,
+    },
+  )?;
+
+  increment_city_counter(store)?;
+
+  Ok(())
+}
+
+// Helper function for updating city metadata and recording the change
+pub fn update_city(
+  store: &mut dyn Storage,
+  city_id: u64,
+  city_name: String,
+  power_level: u8,
+  changed_by: CanonicalAddr,
+  changed_at: Timestamp,
+) -> Result<u64, ContractError> {
+  let mut city = get_city(store, city_id)?;
+  let change_id = city.changes_count;
+
+  // Move name reservation to the new name
+  release_city_name(store, city_id, &city.city_name)?;
+  reserve_city_name(store, city_id, &city_name)?;
+
+  CITY_CHANGE_BY_CITY_ID_AND_CHANGE_ID.save(
+    store,
+    (city_id, change_id),
+    &CityChange {
+      changed_by,
+      changed_at,
+      previous_name: city.city_name,
+      previous_power_level: city.power_level,
+      name: city_name.clone(),
+      power_level,
+    },
+  )?;
+
+  city.city_name = city_name;
+  city.power_level = power_level;
+  city.changes_count += 1;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(change_id)
+}
+
+// Helper function for getting office held by address in city
+pub fn may_get_city_office(
+  store: &dyn Storage,
+  addr: &CanonicalAddr,
+  city_id: u64,
+) -> Result<Option<CityOffice>, ContractError> {
+  Ok(CITY_OFFICE_BY_ADDRESS_AND_CITY_ID.may_load(store, (addr, city_id))?)
+}
+
+// Helper function for setting city mayor, the new mayor leaves deputy office if holds it
+pub fn set_mayor(store: &mut dyn Storage, city_id: u64, mayor: Option<CanonicalAddr>) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+
+  if let Some(previous_mayor) = &city.mayor {
+    CITY_OFFICE_BY_ADDRESS_AND_CITY_ID.remove(store, (previous_mayor, city_id));
+  }
+  if let Some(mayor) = &mayor {
+    if EMPTY_BY_CITY_ID_AND_DEPUTY.has(store, (city_id, mayor)) {
+      EMPTY_BY_CITY_ID_AND_DEPUTY.remove(store, (city_id, mayor));
+      city.deputies_count -= 1;
+    }
+    CITY_OFFICE_BY_ADDRESS_AND_CITY_ID.save(store, (mayor, city_id), &CityOffice::Mayor)?;
+  }
+
+  city.mayor = mayor;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper functions for appointing/dismissing mayor deputies
+pub fn appoint_deputy(store: &mut dyn Storage, city_id: u64, addr: &CanonicalAddr) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  if CITY_OFFICE_BY_ADDRESS_AND_CITY_ID.has(store, (addr, city_id)) {
+    return Err(ContractError::AlreadyCityOfficial {});
+  }
+
+  CITY_OFFICE_BY_ADDRESS_AND_CITY_ID.save(store, (addr, city_id), &CityOffice::Deputy)?;
+  EMPTY_BY_CITY_ID_AND_DEPUTY.save(store, (city_id, addr), &Empty {})?;
+  city.deputies_count += 1;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+pub fn dismiss_deputy(store: &mut dyn Storage, city_id: u64, addr: &CanonicalAddr) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  if !EMPTY_BY_CITY_ID_AND_DEPUTY.has(store, (city_id, addr)) {
+    return Err(ContractError::NotDeputy {});
+  }
+
+  CITY_OFFICE_BY_ADDRESS_AND_CITY_ID.remove(store, (addr, city_id));
+  EMPTY_BY_CITY_ID_AND_DEPUTY.remove(store, (city_id, addr));
+  city.deputies_count -= 1;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper function for removing all city offices, number of deputies is limited
+fn clear_city_offices(store: &mut dyn Storage, city_id: u64) -> Result<(), ContractError> {
+  set_mayor(store, city_id, None)?;
+
+  let deputies = get_city_deputies(store, city_id, None, u64::MAX)?;
+  for deputy in deputies.iter() {
+    dismiss_deputy(store, city_id, deputy)?;
+  }
+
+  Ok(())
+}
+
+// Helper functions for listing city deputies and cities administered by address
+pub fn get_city_deputies(
+  store: &dyn Storage,
+  city_id: u64,
+  start_after: Option<CanonicalAddr>,
+  limit: u64,
+) -> Result<Vec<CanonicalAddr>, ContractError> {
+  Ok(
+    EMPTY_BY_CITY_ID_AND_DEPUTY
+      .prefix(city_id)
+      .keys(
+        store,
+        start_after.map(|addr| Bound::ExclusiveRaw(addr.to_vec())),
+        None,
+        Order::Ascending,
+      )
+      .take(limit as usize)
+      .map(|item| item.map(CanonicalAddr::from))
+      .collect::<StdResult<Vec<CanonicalAddr>>>()?,
+  )
+}
+pub fn get_administered_cities(
+  store: &dyn Storage,
+  addr: &CanonicalAddr,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<Vec<(u64, CityOffice)>, ContractError> {
+  Ok(
+    CITY_OFFICE_BY_ADDRESS_AND_CITY_ID
+      .prefix(addr)
+      .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+      .take(limit as usize)
+      .collect::<StdResult<Vec<(u64, CityOffice)>>>()?,
+  )
+}
+
+// Helper function for setting city lifecycle stage
+pub fn set_city_status(store: &mut dyn Storage, city_id: u64, status: CityStatus) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  city.status = status;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper function for setting city join policy
+pub fn set_join_policy(store: &mut dyn Storage, city_id: u64, join_policy: JoinPolicy) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  city.join_policy = join_policy;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper functions for checking/creating/revoking city invites
+pub fn has_city_invite(store: &dyn Storage, city_id: u64, addr: &CanonicalAddr) -> bool {
+  EMPTY_BY_CITY_ID_AND_INVITEE.has(store, (city_id, addr))
+}
+pub fn set_city_invite(store: &mut dyn Storage, city_id: u64, addr: &CanonicalAddr) -> Result<(), ContractError> {
+  EMPTY_BY_CITY_ID_AND_INVITEE.save(store, (city_id, addr), &Empty {})?;
+
+  Ok(())
+}
+pub fn remove_city_invite(store: &mut dyn Storage, city_id: u64, addr: &CanonicalAddr) {
+  EMPTY_BY_CITY_ID_AND_INVITEE.remove(store, (city_id, addr));
+}
+
+// Helper functions for loading/saving/removing invite codes
+pub fn may_get_invite_code(
+  store: &dyn Storage,
+  city_id: u64,
+  code_hash: &[u8],
+) -> Result<Option<InviteCode>, ContractError> {
+  Ok(INVITE_CODE_BY_CITY_ID_AND_CODE_HASH.may_load(store, (city_id, code_hash))?)
+}
+pub fn set_invite_code(
+  store: &mut dyn Storage,
+  city_id: u64,
+  code_hash: &[u8],
+  invite_code: &InviteCode,
+) -> Result<(), ContractError> {
+  INVITE_CODE_BY_CITY_ID_AND_CODE_HASH.save(store, (city_id, code_hash), invite_code)?;
+
+  Ok(())
+}
+pub fn remove_invite_code(store: &mut dyn Storage, city_id: u64, code_hash: &[u8]) {
+  INVITE_CODE_BY_CITY_ID_AND_CODE_HASH.remove(store, (city_id, code_hash));
+}
+
+// Helper function for using invite code once, the code is removed once it is used up
+pub fn use_invite_code(
+  store: &mut dyn Storage,
+  block: &BlockInfo,
+  city_id: u64,
+  code_hash: &[u8],
+) -> Result<(), ContractError> {
+  let mut invite_code = may_get_invite_code(store, city_id, code_hash)?.ok_or(ContractError::InvalidInviteCode {})?;
+  if invite_code.expires_at.is_expired(block) {
+    return Err(ContractError::InviteCodeExpired {});
+  }
+
+  invite_code.remaining_uses -= 1;
+  if invite_code.remaining_uses == 0 {
+    remove_invite_code(store, city_id, code_hash);
+  } else {
+    set_invite_code(store, city_id, code_hash, &invite_code)?;
+  }
+
+  Ok(())
+}
+
+// Helper function for loading/setting/removing city ban of person
+pub fn may_get_city_ban(
+  store: &dyn Storage,
+  city_id: u64,
+  addr: &CanonicalAddr,
+) -> Result<Option<CityBan>, ContractError> {
+  Ok(CITY_BAN_BY_CITY_ID_AND_PERSON_ADDRESS.may_load(store, (city_id, addr))?)
+}
+pub fn remove_city_ban(store: &mut dyn Storage, city_id: u64, addr: &CanonicalAddr) {
+  CITY_BAN_BY_CITY_ID_AND_PERSON_ADDRESS.remove(store, (city_id, addr));
+}
+
+// Helper function for banning person from city
+// Banned person leaves the city, its waitlist and join requests, kept join fees are refunded
+pub fn ban_from_city(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  city_id: u64,
+  addr: &CanonicalAddr,
+  ban: &CityBan,
+) -> Result<(), ContractError> {
+  if PERSON_IN_CITY_ID_AND_CITY_IN_PERSON_ID_BY_PERSON_ADDRESS_AND_CITY_ID.has(store, (addr, city_id)) {
+    unregister_from_city(store, now, addr.clone(), city_id)?;
+  }
+  if WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.has(store, (addr, city_id)) {
+    leave_waitlist(store, addr, city_id)?;
+  }
+  remove_join_request(store, city_id, addr);
+  remove_city_invite(store, city_id, addr);
+  settle_join_fee(store, city_id, addr, false)?;
+
+  CITY_BAN_BY_CITY_ID_AND_PERSON_ADDRESS.save(store, (city_id, addr), ban)?;
+
+  Ok(())
+}
+
+// Helper function for listing city bans
+pub fn get_city_bans(
+  store: &dyn Storage,
+  city_id: u64,
+  start_after: Option<CanonicalAddr>,
+  limit: u64,
+) -> Result<Vec<(CanonicalAddr, CityBan)>, ContractError> {
+  Ok(
+    CITY_BAN_BY_CITY_ID_AND_PERSON_ADDRESS
+      .prefix(city_id)
+      .range(
+        store,
+        start_after.map(|addr| Bound::ExclusiveRaw(addr.to_vec())),
+        None,
+        Order::Ascending,
+      )
+      .take(limit as usize)
+      .map(|item| item.map(|(addr, ban)| (CanonicalAddr::from(addr), ban)))
+      .collect::<StdResult<Vec<(CanonicalAddr, CityBan)>>>()?,
+  )
+}
+
+// Helper functions for loading/creating/removing join requests
+pub fn get_join_request(store: &dyn Storage, city_id: u64, addr: &CanonicalAddr) -> Result<JoinRequest, ContractError> {
+  wrap_not_found(JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS.load(store, (city_id, addr)))
+}
+pub fn create_join_request(
+  store: &mut dyn Storage,
+  block: &BlockInfo,
+  addr: CanonicalAddr,
+  city_id: u64,
+  request: &JoinRequest,
+) -> Result<(), ContractError> {
+  check_joinable(store, block.time, &addr, city_id)?;
+
+  // Expired join request is replaced
+  if let Some(pending) = JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS.may_load(store, (city_id, &addr))? {
+    if !pending.expires_at.is_expired(block) {
+      return Err(ContractError::JoinRequestPending {});
+    }
+  }
+
+  JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS.save(store, (city_id, &addr), request)?;
+  EMPTY_BY_PERSON_ADDRESS_AND_JOIN_REQUEST_CITY_ID.save(store, (&addr, city_id), &Empty {})?;
+
+  Ok(())
+}
+pub fn remove_join_request(store: &mut dyn Storage, city_id: u64, addr: &CanonicalAddr) {
+  JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS.remove(store, (city_id, addr));
+  EMPTY_BY_PERSON_ADDRESS_AND_JOIN_REQUEST_CITY_ID.remove(store, (addr, city_id));
+}
+
+// Helper functions for listing join requests of city and person
+pub fn get_city_join_requests(
+  store: &dyn Storage,
+  city_id: u64,
+  start_after: Option<CanonicalAddr>,
+  limit: u64,
+) -> Result<Vec<(CanonicalAddr, JoinRequest)>, ContractError> {
+  Ok(
+    JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS
+      .prefix(city_id)
+      .range(
+        store,
+        start_after.map(|addr| Bound::ExclusiveRaw(addr.to_vec())),
+        None,
+        Order::Ascending,
+      )
+      .take(limit as usize)
+      .map(|item| item.map(|(addr, request)| (CanonicalAddr::from(addr), request)))
+      .collect::<StdResult<Vec<(CanonicalAddr, JoinRequest)>>>()?,
+  )
+}
+pub fn get_person_join_requests(
+  store: &dyn Storage,
+  addr: &CanonicalAddr,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<Vec<(u64, JoinRequest)>, ContractError> {
+  EMPTY_BY_PERSON_ADDRESS_AND_JOIN_REQUEST_CITY_ID
+    .prefix(addr)
+    .keys(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+    .take(limit as usize)
+    .map(|item| {
+      let city_id = item?;
+      Ok((
+        city_id,
+        JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS.load(store, (city_id, addr))?,
+      ))
+    })
+    .collect()
+}
+
+// Helper function for setting optional city metadata
+pub fn set_city_profile(store: &mut dyn Storage, city_id: u64, profile: CityProfile) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+
+  // Move city in coordinates index
+  if let Some(coordinates) = &city.profile.coordinates {
+    CITY_ID_BY_GEOHASH.remove(store, &geohash_key(coordinates, city_id));
+  }
+  if let Some(coordinates) = &profile.coordinates {
+    CITY_ID_BY_GEOHASH.save(store, &geohash_key(coordinates, city_id), &city_id)?;
+  }
+
+  city.profile = profile;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper function for setting city age limits
+pub fn set_city_age_limits(
+  store: &mut dyn Storage,
+  city_id: u64,
+  min_age: Option<u8>,
+  max_age: Option<u8>,
+) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  city.min_age = min_age;
+  city.max_age = max_age;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper function for unregistering city members over maximal age of the city or regions containing it
+// Scans at most limit members starting from start_id
+// Returns number of evicted members and identifier to continue from, the sweep is finished once it reaches population
+pub fn evict_overage_batch(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  city_id: u64,
+  start_id: u64,
+  limit: u64,
+) -> Result<(u64, u64), ContractError> {
+  let city = get_city(store, city_id)?;
+  let (_, max_age) = get_age_limits(store, &city)?;
+  let max_age = match max_age {
+    Some(max_age) => max_age as u64,
+    None => return Ok((0, city.members_count)),
+  };
+
+  let mut evicted = 0;
+  let mut person_in_city = start_id;
+  for _ in 0..limit {
+    if person_in_city >= get_city(store, city_id)?.members_count {
+      break;
+    }
+
+    // Unregistering moves the last member to the free slot, so the slot is scanned again
+    let addr = PERSON_ADDRESS_BY_CITY_ID_AND_PERSON_IN_CITY_ID.load(store, (city_id, person_in_city))?;
+    if get_person(store, addr.clone())?.birthday.age_at(now) > max_age {
+      unregister_from_city(store, now, addr, city_id)?;
+      evicted += 1;
+    } else {
+      person_in_city += 1;
+    }
+  }
+
+  Ok((evicted, person_in_city))
+}
+
+// Helper function for setting maximal number of city members
+pub fn set_city_capacity(store: &mut dyn Storage, city_id: u64, max_members: Option<u64>) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  city.max_members = max_members;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper function for registering person in city or putting the person on waitlist if the city is full
+// Returns true if the person is registered
+pub fn join_city(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  addr: CanonicalAddr,
+  city_id: u64,
+) -> Result<bool, ContractError> {
+  let (_, mut city) = check_joinable(store, now, &addr, city_id)?;
+
+  // People waiting for free slot are admitted first
+  let full = match city.max_members {
+    Some(max_members) => city.members_count >= max_members || city.waitlist_count > 0,
+    None => false,
+  };
+  if !full {
+    register_in_city(store, now, addr, city_id)?;
+    return Ok(true);
+  }
+
+  if WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.has(store, (&addr, city_id)) {
+    return Err(ContractError::AlreadyWaitlisted {});
+  }
+
+  // Put person on the end of waitlist
+  let ticket = city.waitlist_tickets_count;
+  PERSON_ADDRESS_BY_CITY_ID_AND_WAITLIST_TICKET.save(store, (city_id, ticket), &addr)?;
+  WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.save(store, (&addr, city_id), &ticket)?;
+  city.waitlist_tickets_count += 1;
+  city.waitlist_count += 1;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  // Admitting head of waitlist if the city has free slot left
+  admit_from_waitlist(store, now, city_id, 1)?;
+
+  Ok(!WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.has(store, (&addr, city_id)))
+}
+
+// Helper function for removing person from city waitlist
+pub fn leave_waitlist(store: &mut dyn Storage, addr: &CanonicalAddr, city_id: u64) -> Result<(), ContractError> {
+  let ticket = WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID
+    .may_load(store, (addr, city_id))?
+    .ok_or(ContractError::NotWaitlisted {})?;
+
+  PERSON_ADDRESS_BY_CITY_ID_AND_WAITLIST_TICKET.remove(store, (city_id, ticket));
+  WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.remove(store, (addr, city_id));
+
+  // Waitlist of deleted city is removed with the city
+  if let Some(mut city) = CITY_BY_ID.may_load(store, city_id)? {
+    city.waitlist_count -= 1;
+    CITY_BY_ID.save(store, city_id, &city)?;
+  }
+
+  Ok(())
+}
+
+// Helper function for admitting people from the head of waitlist while the city has free slots
+// People who are not able to join the city anymore are removed from waitlist
+// Returns number of admitted people
+pub fn admit_from_waitlist(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  city_id: u64,
+  limit: u64,
+) -> Result<u64, ContractError> {
+  let mut admitted = 0;
+
+  for _ in 0..limit {
+    let city = get_city(store, city_id)?;
+    if city.status != CityStatus::Active || city.members_count >= city.max_members.unwrap_or(u64::MAX) {
+      break;
+    }
+
+    // Get head of waitlist
+    let head = PERSON_ADDRESS_BY_CITY_ID_AND_WAITLIST_TICKET
+      .prefix(city_id)
+      .range(store, None, None, Order::Ascending)
+      .next()
+      .transpose()?;
+    let addr = match head {
+      Some((_, addr)) => addr,
+      None => break,
+    };
+
+    leave_waitlist(store, &addr, city_id)?;
+    if check_joinable(store, now, &addr, city_id).is_ok() {
+      register_in_city(store, now, addr.clone(), city_id)?;
+      settle_join_fee(store, city_id, &addr, true)?;
+      admitted += 1;
+    } else {
+      settle_join_fee(store, city_id, &addr, false)?;
+    }
+  }
+
+  Ok(admitted)
+}
+
+// Helper function for getting number of people ahead of person on city waitlist
+pub fn may_get_waitlist_position(
+  store: &dyn Storage,
+  addr: &CanonicalAddr,
+  city_id: u64,
+) -> Result<Option<u64>, ContractError> {
+  let ticket = match WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.may_load(store, (addr, city_id))? {
+    Some(ticket) => ticket,
+    None => return Ok(None),
+  };
+
+  Ok(Some(
+    PERSON_ADDRESS_BY_CITY_ID_AND_WAITLIST_TICKET
+      .prefix(city_id)
+      .keys(store, None, Some(Bound::exclusive(ticket)), Order::Ascending)
+      .count() as u64,
+  ))
 }
-pub fn get_city(store: &dyn Storage, city_id: u64) -> Result<City, ContractError> {
-  Ok(wrap_not_found(CITY_BY_ID.load(store, city_id))?)
+
+// Helper function for removing batch of people from the head of city waitlist, their join fees are refunded
+// Returns number of removed people
+fn remove_waitlist_batch(store: &mut dyn Storage, city_id: u64, batch_size: u64) -> Result<u64, ContractError> {
+  let waiting = PERSON_ADDRESS_BY_CITY_ID_AND_WAITLIST_TICKET
+    .prefix(city_id)
+    .range(store, None, None, Order::Ascending)
+    .take(batch_size as usize)
+    .map(|item| item.map(|(_, addr)| addr))
+    .collect::<StdResult<Vec<CanonicalAddr>>>()?;
+  for addr in waiting.iter() {
+    leave_waitlist(store, addr, city_id)?;
+    settle_join_fee(store, city_id, addr, false)?;
+  }
+
+  Ok(waiting.len() as u64)
 }
 
-// Helper functions for accessing person/city from city/person data
-pub fn get_city_id_by_person(store: &dyn Storage, addr: CanonicalAddr, index: u64) -> Result<u64, ContractError> {
-  Ok(CITY_ID_BY_PERSON_ADDRESS_AND_CITY_IN_PERSON_ID.load(store, (&addr, index))?)
+// Helper function for starting merge of the city into another one, the city is not joinable anymore
+pub fn start_city_merge(store: &mut dyn Storage, from: u64, into: u64) -> Result<(), ContractError> {
+  let mut city = get_city(store, from)?;
+  city.status = CityStatus::Merging;
+  city.merged_into = Some(into);
+  CITY_BY_ID.save(store, from, &city)?;
+
+  let mut target = get_city(store, into)?;
+  target.merges_in_progress += 1;
+  CITY_BY_ID.save(store, into, &target)?;
+
+  Ok(())
 }
-pub fn get_person_address_by_city(
+
+// Helper function for removing batch of waiting people and moving batch of the last members to the city merged into
+// Members are moved regardless of join policy and capacity of the city merged into, their pending join requests and
+// waitlist places there are removed and kept join fees are refunded
+// Members banned from the city merged into or out of its age limits stop the merge until they are kicked or restrictions
+// are lifted
+// The merged city is archived once it has no members and waiting people
+// Returns number of members and waiting people left in the merged city
+pub fn merge_city_batch(
+  store: &mut dyn Storage,
+  block: &BlockInfo,
+  from: u64,
+  into: u64,
+  batch_size: u64,
+) -> Result<u64, ContractError> {
+  // Waiting people are removed first
+  let waiting = remove_waitlist_batch(store, from, batch_size)?;
+
+  let city = get_city(store, from)?;
+  let batch_size = city.members_count.min(batch_size - waiting);
+
+  // Moving the last member does not move other members
+  for i in 1..=batch_size {
+    let addr = PERSON_ADDRESS_BY_CITY_ID_AND_PERSON_IN_CITY_ID.load(store, (from, city.members_count - i))?;
+
+    // People already living in the city merged into are registered there once
+    let banned = may_get_city_ban(store, into, &addr)?.map_or(false, |ban| !ban.until.is_expired(block));
+    let moved = match check_joinable(store, block.time, &addr, into) {
+      Ok(_) if !banned => true,
+      Err(ContractError::PersonAlreadyRegisteredInCity { .. }) => false,
+      Ok((person, target)) => {
+        return Err(ContractError::MemberNotMovable {
+          nickname: person.nickname,
+          city_name: target.city_name,
+        })
+      }
+      Err(ContractError::AgeRestricted { city_name }) => {
+        return Err(ContractError::MemberNotMovable {
+          nickname: get_person(store, addr)?.nickname,
+          city_name,
+        })
+      }
+      Err(err) => return Err(err),
+    };
+
+    unregister_from_city(store, block.time, addr.clone(), from)?;
+    if moved {
+      if WAITLIST_TICKET_BY_PERSON_ADDRESS_AND_CITY_ID.has(store, (&addr, into)) {
+        leave_waitlist(store, &addr, into)?;
+      }
+      remove_join_request(store, into, &addr);
+      settle_join_fee(store, into, &addr, false)?;
+      register_in_city(store, block.time, addr, into)?;
+    }
+  }
+
+  // Archive the city without members and waiting people, it keeps pointer to the city merged into
+  let members_left = city.members_count - batch_size + city.waitlist_count;
+  if members_left == 0 {
+    set_city_status(store, from, CityStatus::Archived)?;
+
+    let mut target = get_city(store, into)?;
+    target.merges_in_progress -= 1;
+    CITY_BY_ID.save(store, into, &target)?;
+  }
+
+  Ok(members_left)
+}
+
+// Helper function for following merged cities to the city their members were moved to
+pub fn resolve_city_id(store: &dyn Storage, city_id: u64) -> Result<u64, ContractError> {
+  let mut city_id = city_id;
+  loop {
+    let city = get_city(store, city_id)?;
+    match city.merged_into {
+      Some(into) if city.status == CityStatus::Archived && CITY_BY_ID.has(store, into) => city_id = into,
+      _ => return Ok(city_id),
+    }
+  }
+}
+
+// Helper function for removing batch of city records and waiting people and unregistering batch of the last city members
+// The city is removed once it has no members, waiting people and records
+// Returns number of members and waiting people left in the city and whether the city is removed
+pub fn delete_city_batch(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  city_id: u64,
+  batch_size: u64,
+) -> Result<(u64, bool), ContractError> {
+  // City records are removed first, then waiting people
+  let cleared = clear_city_records_batch(store, city_id, batch_size)?;
+  let waiting = remove_waitlist_batch(store, city_id, batch_size - cleared)?;
+
+  let city = get_city(store, city_id)?;
+  let unregistered = city.members_count.min(batch_size - cleared - waiting);
+
+  // Unregistering the last member does not move other members
+  for i in 1..=unregistered {
+    let addr = PERSON_ADDRESS_BY_CITY_ID_AND_PERSON_IN_CITY_ID.load(store, (city_id, city.members_count - i))?;
+    unregister_from_city(store, now, addr, city_id)?;
+  }
+
+  // Remove the city without members, waiting people and records and its offices, its history is kept
+  // Records are all removed if they have not filled the batch
+  let members_left = city.members_count - unregistered + city.waitlist_count;
+  let deleted = members_left == 0 && cleared < batch_size;
+  if deleted {
+    clear_city_offices(store, city_id)?;
+    set_city_region(store, city_id, None)?;
+    release_city_name(store, city_id, &city.city_name)?;
+    if let Some(coordinates) = &city.profile.coordinates {
+      CITY_ID_BY_GEOHASH.remove(store, &geohash_key(coordinates, city_id));
+    }
+    CITY_BY_ID.remove(store, city_id);
+  }
+
+  Ok((members_left, deleted))
+}
+
+// Helper function for removing batch of city records: pending join requests with refund of kept join fees,
+// invites, invite codes, bans and join fees in cw20 tokens accepted by the city
+// Returns number of removed records
+fn clear_city_records_batch(store: &mut dyn Storage, city_id: u64, batch_size: u64) -> Result<u64, ContractError> {
+  let requests = city_record_keys(store, &JOIN_REQUEST_BY_CITY_ID_AND_PERSON_ADDRESS, city_id, batch_size)?;
+  for addr in requests.iter() {
+    let addr = CanonicalAddr::from(addr.as_slice());
+    remove_join_request(store, city_id, &addr);
+    settle_join_fee(store, city_id, &addr, false)?;
+  }
+  let mut cleared = requests.len() as u64;
+
+  cleared += remove_city_records(store, &EMPTY_BY_CITY_ID_AND_INVITEE, city_id, batch_size - cleared)?;
+  cleared += remove_city_records(
+    store,
+    &INVITE_CODE_BY_CITY_ID_AND_CODE_HASH,
+    city_id,
+    batch_size - cleared,
+  )?;
+  cleared += remove_city_records(
+    store,
+    &CITY_BAN_BY_CITY_ID_AND_PERSON_ADDRESS,
+    city_id,
+    batch_size - cleared,
+  )?;
+  cleared += remove_city_records(
+    store,
+    &CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN,
+    city_id,
+    batch_size - cleared,
+  )?;
+
+  Ok(cleared)
+}
+fn city_record_keys<T: Serialize + DeserializeOwned>(
   store: &dyn Storage,
+  records: &Map<(u64, &[u8]), T>,
   city_id: u64,
-  index: u64,
-) -> Result<CanonicalAddr, ContractError> {
-  Ok(PERSON_ADDRESS_BY_CITY_ID_AND_PERSON_IN_CITY_ID.load(store, (city_id, index))?)
+  limit: u64,
+) -> Result<Vec<Vec<u8>>, ContractError> {
+  Ok(
+    records
+      .prefix(city_id)
+      .keys_raw(store, None, None, Order::Ascending)
+      .take(limit as usize)
+      .collect(),
+  )
 }
+fn remove_city_records<T: Serialize + DeserializeOwned>(
+  store: &mut dyn Storage,
+  records: &Map<(u64, &[u8]), T>,
+  city_id: u64,
+  limit: u64,
+) -> Result<u64, ContractError> {
+  let keys = city_record_keys(store, records, city_id, limit)?;
+  for key in keys.iter() {
+    records.remove(store, (city_id, key));
+  }
 
-// Helper functions for loading/saving config data
-pub fn get_storage(store: &dyn Storage) -> Result<Config, ContractError> {
-  Ok(CONFIG_INSTANCE.load(store)?)
+  Ok(keys.len() as u64)
 }
-pub fn set_storage(store: &mut dyn Storage, config: &Config) -> Result<(), ContractError> {
-  CONFIG_INSTANCE.save(store, config)?;
+
+// Helper function for setting city join fee
+pub fn set_join_fee(store: &mut dyn Storage, city_id: u64, join_fee: Option<Coin>) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+  city.join_fee = join_fee;
+  CITY_BY_ID.save(store, city_id, &city)?;
 
   Ok(())
 }
 
-// Helper functions for updating counters
-fn increment_city_counter(store: &mut dyn Storage) -> Result<(), ContractError> {
-  let mut config = get_storage(store)?;
-  config.cities_count += 1;
-  set_storage(store, &config)?;
+// Helper functions for loading/setting/listing join fees in cw20 tokens accepted by city
+pub fn may_get_cw20_join_fee(
+  store: &dyn Storage,
+  city_id: u64,
+  token: &CanonicalAddr,
+) -> Result<Option<Uint128>, ContractError> {
+  Ok(CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN.may_load(store, (city_id, token))?)
+}
+pub fn set_cw20_join_fee(
+  store: &mut dyn Storage,
+  city_id: u64,
+  token: &CanonicalAddr,
+  amount: Option<Uint128>,
+) -> Result<(), ContractError> {
+  match amount {
+    Some(amount) => CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN.save(store, (city_id, token), &amount)?,
+    None => CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN.remove(store, (city_id, token)),
+  }
 
   Ok(())
 }
-fn crement_person_and_city_counters(
+pub fn has_cw20_join_fees(store: &dyn Storage, city_id: u64) -> bool {
+  CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN
+    .prefix(city_id)
+    .keys_raw(store, None, None, Order::Ascending)
+    .next()
+    .is_some()
+}
+pub fn get_cw20_join_fees(store: &dyn Storage, city_id: u64) -> Result<Vec<(CanonicalAddr, Uint128)>, ContractError> {
+  list_cw20_balances(store, CW20_JOIN_FEE_BY_CITY_ID_AND_TOKEN.prefix(city_id))
+}
+
+// Helper functions for keeping join fee paid by person until the person is registered in city
+// Join fee kept for previous attempt is refunded
+pub fn escrow_join_fee(
   store: &mut dyn Storage,
-  addr: CanonicalAddr,
   city_id: u64,
-  increment: bool,
+  addr: &CanonicalAddr,
+  fee: &Coin,
 ) -> Result<(), ContractError> {
-  // Load
-  let mut person = get_person(store, addr.clone())?;
-  let mut city = get_city(store, city_id)?;
+  settle_join_fee(store, city_id, addr, false)?;
+  JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.save(store, (city_id, addr), fee)?;
 
-  // Change
-  if increment {
-    person.cities_count += 1;
-    city.members_count += 1;
-  } else {
-    person.cities_count -= 1;
-    city.members_count -= 1;
+  Ok(())
+}
+pub fn escrow_cw20_join_fee(
+  store: &mut dyn Storage,
+  city_id: u64,
+  addr: &CanonicalAddr,
+  token: &CanonicalAddr,
+  amount: Uint128,
+) -> Result<(), ContractError> {
+  settle_join_fee(store, city_id, addr, false)?;
+  CW20_JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.save(store, (city_id, addr), &(token.clone(), amount))?;
+
+  Ok(())
+}
+
+// Helper function for releasing join fee kept for person
+// The fee is credited to city treasury if the person is registered, otherwise it is refunded to the person
+// Protocol share of credited fee is credited to protocol treasury
+pub fn settle_join_fee(
+  store: &mut dyn Storage,
+  city_id: u64,
+  addr: &CanonicalAddr,
+  registered: bool,
+) -> Result<(), ContractError> {
+  let protocol_fee_share = get_storage(store)?.protocol_fee_share;
+
+  if let Some(fee) = JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.may_load(store, (city_id, addr))? {
+    JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.remove(store, (city_id, addr));
+
+    if registered {
+      let share = fee.amount.multiply_ratio(protocol_fee_share, BASIS_POINTS);
+      add_to_balance(store, PROTOCOL_BALANCE_BY_DENOM, &fee.denom, share)?;
+      add_to_balance(
+        store,
+        BALANCE_BY_CITY_ID_AND_DENOM,
+        (city_id, &fee.denom),
+        fee.amount - share,
+      )?;
+    } else {
+      add_to_balance(
+        store,
+        REFUND_BY_PERSON_ADDRESS_AND_DENOM,
+        (addr, &fee.denom),
+        fee.amount,
+      )?;
+    }
   }
 
-  // Save
-  PERSON_BY_ADDRESS.save(store, &addr, &person)?;
-  CITY_BY_ID.save(store, city_id, &city)?;
+  if let Some((token, amount)) = CW20_JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.may_load(store, (city_id, addr))? {
+    CW20_JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.remove(store, (city_id, addr));
+
+    if registered {
+      let share = amount.multiply_ratio(protocol_fee_share, BASIS_POINTS);
+      add_to_balance(store, CW20_PROTOCOL_BALANCE_BY_TOKEN, &token, share)?;
+      add_to_balance(
+        store,
+        CW20_BALANCE_BY_CITY_ID_AND_TOKEN,
+        (city_id, &token),
+        amount - share,
+      )?;
+    } else {
+      add_to_balance(store, CW20_REFUND_BY_PERSON_ADDRESS_AND_TOKEN, (addr, &token), amount)?;
+    }
+  }
 
   Ok(())
 }
+pub fn may_get_escrowed_join_fee(
+  store: &dyn Storage,
+  city_id: u64,
+  addr: &CanonicalAddr,
+) -> Result<Option<Coin>, ContractError> {
+  Ok(JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.may_load(store, (city_id, addr))?)
+}
+pub fn may_get_escrowed_cw20_join_fee(
+  store: &dyn Storage,
+  city_id: u64,
+  addr: &CanonicalAddr,
+) -> Result<Option<(CanonicalAddr, Uint128)>, ContractError> {
+  Ok(CW20_JOIN_FEE_BY_CITY_ID_AND_PERSON_ADDRESS.may_load(store, (city_id, addr))?)
+}
 
-// Helper function for adding new city
-pub fn create_city(store: &mut dyn Storage, city_name: String, power_level: u8) -> Result<(), ContractError> {
-  let config = get_storage(store)?.cities_count;
-  CITY_BY_ID.save(
+// Helper functions for listing city treasury, protocol treasury and refund balances
+pub fn get_city_balances(store: &dyn Storage, city_id: u64) -> Result<Vec<Coin>, ContractError> {
+  list_balances(store, BALANCE_BY_CITY_ID_AND_DENOM.prefix(city_id))
+}
+pub fn get_city_cw20_balances(
+  store: &dyn Storage,
+  city_id: u64,
+) -> Result<Vec<(CanonicalAddr, Uint128)>, ContractError> {
+  list_cw20_balances(store, CW20_BALANCE_BY_CITY_ID_AND_TOKEN.prefix(city_id))
+}
+pub fn get_protocol_balances(store: &dyn Storage) -> Result<Vec<Coin>, ContractError> {
+  Ok(
+    PROTOCOL_BALANCE_BY_DENOM
+      .range(store, None, None, Order::Ascending)
+      .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+      .collect::<StdResult<Vec<Coin>>>()?,
+  )
+}
+pub fn get_protocol_cw20_balances(store: &dyn Storage) -> Result<Vec<(CanonicalAddr, Uint128)>, ContractError> {
+  Ok(
+    CW20_PROTOCOL_BALANCE_BY_TOKEN
+      .range(store, None, None, Order::Ascending)
+      .map(|item| item.map(|(token, amount)| (CanonicalAddr::from(token), amount)))
+      .collect::<StdResult<Vec<(CanonicalAddr, Uint128)>>>()?,
+  )
+}
+pub fn get_refunds(store: &dyn Storage, addr: &CanonicalAddr) -> Result<Vec<Coin>, ContractError> {
+  list_balances(store, REFUND_BY_PERSON_ADDRESS_AND_DENOM.prefix(addr))
+}
+pub fn get_cw20_refunds(
+  store: &dyn Storage,
+  addr: &CanonicalAddr,
+) -> Result<Vec<(CanonicalAddr, Uint128)>, ContractError> {
+  list_cw20_balances(store, CW20_REFUND_BY_PERSON_ADDRESS_AND_TOKEN.prefix(addr))
+}
+
+// Helper functions for withdrawing from city treasury, protocol treasury and refund balances
+// Whole balance is withdrawn if amount is not provided, returns withdrawn amount
+pub fn withdraw_city_balance(
+  store: &mut dyn Storage,
+  city_id: u64,
+  denom: &str,
+  amount: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+  withdraw_from_balance(store, BALANCE_BY_CITY_ID_AND_DENOM, (city_id, denom), amount)
+}
+pub fn withdraw_city_cw20_balance(
+  store: &mut dyn Storage,
+  city_id: u64,
+  token: &CanonicalAddr,
+  amount: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+  withdraw_from_balance(store, CW20_BALANCE_BY_CITY_ID_AND_TOKEN, (city_id, token), amount)
+}
+pub fn withdraw_protocol_balance(
+  store: &mut dyn Storage,
+  denom: &str,
+  amount: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+  withdraw_from_balance(store, PROTOCOL_BALANCE_BY_DENOM, denom, amount)
+}
+pub fn withdraw_protocol_cw20_balance(
+  store: &mut dyn Storage,
+  token: &CanonicalAddr,
+  amount: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+  withdraw_from_balance(store, CW20_PROTOCOL_BALANCE_BY_TOKEN, token, amount)
+}
+pub fn withdraw_refund(store: &mut dyn Storage, addr: &CanonicalAddr, denom: &str) -> Result<Uint128, ContractError> {
+  withdraw_from_balance(store, REFUND_BY_PERSON_ADDRESS_AND_DENOM, (addr, denom), None)
+}
+pub fn withdraw_cw20_refund(
+  store: &mut dyn Storage,
+  addr: &CanonicalAddr,
+  token: &CanonicalAddr,
+) -> Result<Uint128, ContractError> {
+  withdraw_from_balance(store, CW20_REFUND_BY_PERSON_ADDRESS_AND_TOKEN, (addr, token), None)
+}
+
+// Helper function for adding amount to balance stored by key
+fn add_to_balance<'a, K: PrimaryKey<'a>>(
+  store: &mut dyn Storage,
+  map: Map<'a, K, Uint128>,
+  key: K,
+  amount: Uint128,
+) -> Result<(), ContractError> {
+  if !amount.is_zero() {
+    map.update(store, key, |balance| -> StdResult<_> {
+      Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+  }
+
+  Ok(())
+}
+
+// Helper function for subtracting amount from balance stored by key, empty balance is removed
+fn withdraw_from_balance<'a, K: PrimaryKey<'a> + Clone>(
+  store: &mut dyn Storage,
+  map: Map<'a, K, Uint128>,
+  key: K,
+  amount: Option<Uint128>,
+) -> Result<Uint128, ContractError> {
+  let balance = map.may_load(store, key.clone())?.unwrap_or_default();
+  let amount = amount.unwrap_or(balance);
+  if amount.is_zero() || amount > balance {
+    return Err(ContractError::InsufficientBalance { balance });
+  }
+
+  if amount == balance {
+    map.remove(store, key);
+  } else {
+    map.save(store, key, &(balance - amount))?;
+  }
+
+  Ok(amount)
+}
+
+// Helper functions for listing balances stored by denom or cw20 token address
+fn list_balances(store: &dyn Storage, balances: Prefix<&str, Uint128, &str>) -> Result<Vec<Coin>, ContractError> {
+  Ok(
+    balances
+      .range(store, None, None, Order::Ascending)
+      .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+      .collect::<StdResult<Vec<Coin>>>()?,
+  )
+}
+fn list_cw20_balances(
+  store: &dyn Storage,
+  balances: Prefix<&[u8], Uint128, &[u8]>,
+) -> Result<Vec<(CanonicalAddr, Uint128)>, ContractError> {
+  Ok(
+    balances
+      .range(store, None, None, Order::Ascending)
+      .map(|item| item.map(|(token, amount)| (CanonicalAddr::from(token), amount)))
+      .collect::<StdResult<Vec<(CanonicalAddr, Uint128)>>>()?,
+  )
+}
+
+// Helper function for listing existing cities
+pub fn get_cities(store: &dyn Storage, start_id: u64) -> impl Iterator<Item = Result<(u64, City), ContractError>> + '_ {
+  CITY_BY_ID
+    .range(store, Some(Bound::inclusive(start_id)), None, Order::Ascending)
+    .map(|item| Ok(item?))
+}
+
+// Helper function for listing city changes
+pub fn get_city_changes(
+  store: &dyn Storage,
+  city_id: u64,
+  start_after: Option<u64>,
+) -> impl Iterator<Item = Result<(u64, CityChange), ContractError>> + '_ {
+  CITY_CHANGE_BY_CITY_ID_AND_CHANGE_ID
+    .prefix(city_id)
+    .range(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+    .map(|item| Ok(item?))
+}
+
+// Helper functions for loading/creating/updating/removing regions
+pub fn get_region(store: &dyn Storage, region_id: u64) -> Result<Region, ContractError> {
+  wrap_not_found(REGION_BY_ID.load(store, region_id))
+}
+pub fn create_region(store: &mut dyn Storage, name: RegionName, parent_id: Option<u64>) -> Result<u64, ContractError> {
+  let mut config = get_storage(store)?;
+  let region_id = config.regions_count;
+
+  // Regions are placed under countries, countries have no parent
+  let kind = match parent_id {
+    Some(parent_id) => {
+      let mut parent = get_region(store, parent_id)?;
+      if parent.kind != RegionKind::Country {
+        return Err(ContractError::InvalidRegionParent {});
+      }
+      parent.children_count += 1;
+      REGION_BY_ID.save(store, parent_id, &parent)?;
+      EMPTY_BY_PARENT_ID_AND_REGION_ID.save(store, (parent_id, region_id), &Empty {})?;
+
+      RegionKind::Region
+    }
+    None => {
+      EMPTY_BY_COUNTRY_ID.save(store, region_id, &Empty {})?;
+
+      RegionKind::Country
+    }
+  };
+
+  REGION_BY_ID.save(
     store,
-    config,
-    &City {
-      city_name: city_name,
-      power_level: power_level,
-      members_count: 0,
+    region_id,
+    &Region {
+      name,
+      kind,
+      parent_id,
+      children_count: 0,
+      cities_count: 0,
+      population: 0,
+      min_age: None,
+      max_age: None,
     },
   )?;
 
-  increment_city_counter(store)?;
+  config.regions_count += 1;
+  set_storage(store, &config)?;
+
+  Ok(region_id)
+}
+pub fn update_region(
+  store: &mut dyn Storage,
+  region_id: u64,
+  name: RegionName,
+  min_age: Option<u8>,
+  max_age: Option<u8>,
+) -> Result<(), ContractError> {
+  let mut region = get_region(store, region_id)?;
+  region.name = name;
+  region.min_age = min_age;
+  region.max_age = max_age;
+  REGION_BY_ID.save(store, region_id, &region)?;
+
+  Ok(())
+}
+pub fn remove_region(store: &mut dyn Storage, region_id: u64) -> Result<(), ContractError> {
+  let region = get_region(store, region_id)?;
+  if region.children_count > 0 || region.cities_count > 0 {
+    return Err(ContractError::RegionNotEmpty {});
+  }
+
+  match region.parent_id {
+    Some(parent_id) => {
+      let mut parent = get_region(store, parent_id)?;
+      parent.children_count -= 1;
+      REGION_BY_ID.save(store, parent_id, &parent)?;
+      EMPTY_BY_PARENT_ID_AND_REGION_ID.remove(store, (parent_id, region_id));
+    }
+    None => EMPTY_BY_COUNTRY_ID.remove(store, region_id),
+  }
+  REGION_BY_ID.remove(store, region_id);
+
+  Ok(())
+}
+
+// Helper function for moving city to another region or out of any region, city population is moved with the city
+pub fn set_city_region(store: &mut dyn Storage, city_id: u64, region_id: Option<u64>) -> Result<(), ContractError> {
+  let mut city = get_city(store, city_id)?;
+
+  // Cities are placed in regions only
+  if let Some(region_id) = region_id {
+    if get_region(store, region_id)?.kind != RegionKind::Region {
+      return Err(ContractError::InvalidRegionParent {});
+    }
+  }
+
+  if let Some(previous_id) = city.region_id {
+    let mut previous = get_region(store, previous_id)?;
+    previous.cities_count -= 1;
+    REGION_BY_ID.save(store, previous_id, &previous)?;
+    EMPTY_BY_REGION_ID_AND_CITY_ID.remove(store, (previous_id, city_id));
+    change_region_population(store, previous_id, city.members_count, false)?;
+  }
+
+  if let Some(region_id) = region_id {
+    let mut region = get_region(store, region_id)?;
+    region.cities_count += 1;
+    REGION_BY_ID.save(store, region_id, &region)?;
+    EMPTY_BY_REGION_ID_AND_CITY_ID.save(store, (region_id, city_id), &Empty {})?;
+    change_region_population(store, region_id, city.members_count, true)?;
+  }
+
+  city.region_id = region_id;
+  CITY_BY_ID.save(store, city_id, &city)?;
+
+  Ok(())
+}
+
+// Helper function for changing population of region and all regions containing it
+fn change_region_population(
+  store: &mut dyn Storage,
+  region_id: u64,
+  amount: u64,
+  increase: bool,
+) -> Result<(), ContractError> {
+  for (id, mut region) in get_region_path(store, region_id)? {
+    if increase {
+      region.population += amount;
+    } else {
+      region.population -= amount;
+    }
+    REGION_BY_ID.save(store, id, &region)?;
+  }
 
   Ok(())
 }
 
+// Helper function for getting region and all regions containing it up to country
+pub fn get_region_path(store: &dyn Storage, region_id: u64) -> Result<Vec<(u64, Region)>, ContractError> {
+  let mut path = vec![];
+  let mut next_id = Some(region_id);
+  while let Some(id) = next_id {
+    let region = get_region(store, id)?;
+    next_id = region.parent_id;
+    path.push((id, region));
+  }
+
+  Ok(path)
+}
+
+// Helper function for listing child regions of country or countries if parent is not provided
+pub fn get_child_regions(
+  store: &dyn Storage,
+  parent_id: Option<u64>,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<Vec<(u64, Region)>, ContractError> {
+  let start = start_after.map(Bound::exclusive);
+  let ids = match parent_id {
+    Some(parent_id) => EMPTY_BY_PARENT_ID_AND_REGION_ID
+      .prefix(parent_id)
+      .keys(store, start, None, Order::Ascending)
+      .take(limit as usize)
+      .collect::<StdResult<Vec<u64>>>()?,
+    None => EMPTY_BY_COUNTRY_ID
+      .keys(store, start, None, Order::Ascending)
+      .take(limit as usize)
+      .collect::<StdResult<Vec<u64>>>()?,
+  };
+
+  ids.into_iter().map(|id| Ok((id, get_region(store, id)?))).collect()
+}
+
+// Helper function for listing cities of region
+pub fn get_region_cities(
+  store: &dyn Storage,
+  region_id: u64,
+  start_after: Option<u64>,
+  limit: u64,
+) -> Result<Vec<(u64, City)>, ContractError> {
+  EMPTY_BY_REGION_ID_AND_CITY_ID
+    .prefix(region_id)
+    .keys(store, start_after.map(Bound::exclusive), None, Order::Ascending)
+    .take(limit as usize)
+    .map(|item| {
+      let city_id = item?;
+      Ok((city_id, get_city(store, city_id)?))
+    })
+    .collect()
+}
+
 // Helper function for adding new person
 pub fn create_person(
   store: &mut dyn Storage,
@@ -132,9 +1952,9 @@ pub fn create_person(
     store,
     &addr,
     &Person {
-      birthday: birthday,
-      nickname: nickname,
-      email: email,
+      birthday,
+      nickname,
+      email,
       cities_count: 0,
     },
   )?;
@@ -159,14 +1979,26 @@ pub fn update_person(
   Ok(())
 }
 
-// Helper function for registring person in city
-pub fn register_in_city(store: &mut dyn Storage, addr: CanonicalAddr, city_id: u64) -> Result<(), ContractError> {
+// Helper function for checking person is able to join city
+fn check_joinable(
+  store: &dyn Storage,
+  now: Timestamp,
+  addr: &CanonicalAddr,
+  city_id: u64,
+) -> Result<(Person, City), ContractError> {
   let person = get_person(store, addr.clone())?;
   let city = get_city(store, city_id)?;
 
+  // People are able to register in active cities only
+  if city.status != CityStatus::Active {
+    return Err(ContractError::CityArchived {
+      city_name: city.city_name,
+    });
+  }
+
   // There should be no data loaded by may_load if the user is not registered yet
   if let Some((_, _)) =
-    PERSON_IN_CITY_ID_AND_CITY_IN_PERSON_ID_BY_PERSON_ADDRESS_AND_CITY_ID.may_load(store, (&addr, city_id))?
+    PERSON_IN_CITY_ID_AND_CITY_IN_PERSON_ID_BY_PERSON_ADDRESS_AND_CITY_ID.may_load(store, (addr, city_id))?
   {
     return Err(ContractError::PersonAlreadyRegisteredInCity {
       nickname: person.nickname,
@@ -174,6 +2006,43 @@ pub fn register_in_city(store: &mut dyn Storage, addr: CanonicalAddr, city_id: u
     });
   };
 
+  // Person age should be within limits of the city and regions containing it
+  let age = person.birthday.age_at(now);
+  let (min_age, max_age) = get_age_limits(store, &city)?;
+  if age < min_age.unwrap_or(0) as u64 || age > max_age.unwrap_or(u8::MAX) as u64 {
+    return Err(ContractError::AgeRestricted {
+      city_name: city.city_name,
+    });
+  }
+
+  Ok((person, city))
+}
+
+// Helper function for combining age limits of the city and regions containing it into the strictest ones
+fn get_age_limits(store: &dyn Storage, city: &City) -> Result<(Option<u8>, Option<u8>), ContractError> {
+  let (mut min_age, mut max_age) = (city.min_age, city.max_age);
+  if let Some(region_id) = city.region_id {
+    for (_, region) in get_region_path(store, region_id)? {
+      min_age = min_age.max(region.min_age);
+      max_age = match (max_age, region.max_age) {
+        (Some(max_age), Some(region_max_age)) => Some(max_age.min(region_max_age)),
+        (max_age, region_max_age) => max_age.or(region_max_age),
+      };
+    }
+  }
+
+  Ok((min_age, max_age))
+}
+
+// Helper function for registring person in city
+pub fn register_in_city(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  addr: CanonicalAddr,
+  city_id: u64,
+) -> Result<(), ContractError> {
+  let (person, city) = check_joinable(store, now, &addr, city_id)?;
+
   // Adding new person in city
   PERSON_ADDRESS_BY_CITY_ID_AND_PERSON_IN_CITY_ID.save(store, (city_id, city.members_count), &addr)?;
 
@@ -194,7 +2063,12 @@ pub fn register_in_city(store: &mut dyn Storage, addr: CanonicalAddr, city_id: u
 }
 
 // Helper function for registring person in city
-pub fn unregister_from_city(store: &mut dyn Storage, addr: CanonicalAddr, city_id: u64) -> Result<(), ContractError> {
+pub fn unregister_from_city(
+  store: &mut dyn Storage,
+  now: Timestamp,
+  addr: CanonicalAddr,
+  city_id: u64,
+) -> Result<(), ContractError> {
   let person = get_person(store, addr.clone())?;
   let city = get_city(store, city_id)?;
 
@@ -246,5 +2120,8 @@ pub fn unregister_from_city(store: &mut dyn Storage, addr: CanonicalAddr, city_i
   // Decrementing counters
   crement_person_and_city_counters(store, addr, city_id, false)?;
 
+  // Admitting head of waitlist to the free slot
+  admit_from_waitlist(store, now, city_id, 1)?;
+
   Ok(())
 }
@@ -1,6 +1,9 @@
 // The file is responsible for storing all custom structs
 
-use cosmwasm_std::{Addr, CanonicalAddr};
+use cosmwasm_std::{Addr, CanonicalAddr, Coin, Timestamp};
+use cw_utils::Expiration;
+
+use crate::msg::ExecuteMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -14,8 +17,21 @@ pub struct Birthday {
   pub year: u16,
 }
 
+pub const YEAR_IN_SECONDS: u64 = 31556952;
+pub const DAY_IN_SECONDS: u64 = 86400;
+pub const BASIS_POINTS: u64 = 10000;
+
+impl Birthday {
+  // Full years lived by the time, missed day is treated as the last day of year
+  pub fn age_at(&self, time: Timestamp) -> u64 {
+    let born_at = self.year as u64 * YEAR_IN_SECONDS + self.day.unwrap_or(366) as u64 * DAY_IN_SECONDS;
+    (1970 * YEAR_IN_SECONDS + time.seconds()).saturating_sub(born_at) / YEAR_IN_SECONDS
+  }
+}
+
 // Defining such types is important as it keeps code declarative
 pub type CityName = String;
+pub type RegionName = String;
 pub type Nickname = String;
 pub type Email = String;
 
@@ -23,6 +39,46 @@ pub type Email = String;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
   // Important: all addresses should be stored in CanonicalAddr type
+  // Contract owner address, it is cleared forever once renounced
+  pub maintainer: Option<CanonicalAddr>,
+  // Frozen contract has no maintainer access level and could not be managed anymore
+  pub frozen: bool,
+
+  // Counter of registered cities
+  pub cities_count: u64,
+  // Counter of created council proposals
+  pub council_proposals_count: u64,
+  // Counter of started elections
+  pub elections_count: u64,
+  // Counter of created regions
+  pub regions_count: u64,
+
+  // Requirements a person should satisfy to become maintainer
+  pub maintainer_requirement_name: Nickname,
+  pub maintainer_requirement_age: u8,
+  // Seconds maintainer is able to veto maintainer claim
+  pub maintainer_claim_window: u64,
+
+  // Election settings, voting period is in seconds and quorum is minimal total weight of votes
+  pub election_voting_period: u64,
+  pub election_quorum: u64,
+
+  // Maximal number of items returned by list queries
+  pub max_page_size: u64,
+  // Maximal number of city records, waiting people and members removed by single city deletion call or merge call
+  pub city_deletion_batch_size: u64,
+  // Seconds join request is able to be approved
+  pub join_request_period: u64,
+  // Share of join fees credited to maintainer in basis points
+  pub protocol_fee_share: u64,
+
+  // Address able to pause and unpause actions next to maintainer
+  pub guardian: Option<CanonicalAddr>,
+}
+
+// LegacyConfig is layout of Config stored by contract version 1.0.0, it is used for migration only
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
   // Contract owner address
   pub maintainer: CanonicalAddr,
 
@@ -30,6 +86,243 @@ pub struct Config {
   pub cities_count: u64,
 }
 
+// ConfigResponse is struct for representing Config when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+  // Contract owner address
+  pub maintainer: Option<Addr>,
+  // Frozen contract could not be managed anymore
+  pub frozen: bool,
+
+  // Number of registered cities
+  pub cities_count: u64,
+  // Number of created council proposals
+  pub council_proposals_count: u64,
+  // Number of started elections
+  pub elections_count: u64,
+  // Number of created regions
+  pub regions_count: u64,
+
+  // Requirements a person should satisfy to become maintainer
+  pub maintainer_requirement_name: Nickname,
+  pub maintainer_requirement_age: u8,
+  // Seconds maintainer is able to veto maintainer claim
+  pub maintainer_claim_window: u64,
+
+  // Election settings, voting period is in seconds and quorum is minimal total weight of votes
+  pub election_voting_period: u64,
+  pub election_quorum: u64,
+
+  // Maximal number of items returned by list queries
+  pub max_page_size: u64,
+  // Maximal number of city records, waiting people and members removed by single city deletion call or merge call
+  pub city_deletion_batch_size: u64,
+  // Seconds join request is able to be approved
+  pub join_request_period: u64,
+  // Share of join fees credited to maintainer in basis points
+  pub protocol_fee_share: u64,
+
+  // Address able to pause and unpause actions next to maintainer
+  pub guardian: Option<Addr>,
+}
+
+// PausedAction is struct for representing paused action when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PausedAction {
+  // Action name is the message name in JSON representation
+  pub action: String,
+
+  // Pause reason provided by maintainer or guardian
+  pub reason: String,
+}
+
+// MaintainerProposal is struct that is stored in cold storage next to config
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MaintainerProposal {
+  // Address proposed by current maintainer to accept the role
+  pub candidate: CanonicalAddr,
+
+  // Proposal could not be accepted after expiration
+  pub expires_at: Expiration,
+}
+
+// MaintainerProposalResponse is struct for representing MaintainerProposal when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MaintainerProposalResponse {
+  // Proposed maintainer address
+  pub candidate: Addr,
+
+  // Proposal expiration
+  pub expires_at: Expiration,
+}
+
+// MaintainerClaim is struct that is stored in cold storage next to config, it exists only while claim is pending
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MaintainerClaim {
+  // Address satisfied maintainer requirements
+  pub claimant: CanonicalAddr,
+
+  // Claim could be vetoed by maintainer before and finalized by anyone after challenge window end
+  pub finalizable_at: Expiration,
+}
+
+// MaintainerClaimResponse is struct for representing MaintainerClaim when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MaintainerClaimResponse {
+  // Claimant address
+  pub claimant: Addr,
+
+  // Challenge window end
+  pub finalizable_at: Expiration,
+}
+
+// Role is access level granted by maintainer for specific kind of actions, maintainer has all roles implicitly
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+  // Is able to register cities
+  CityCreator,
+  // Is able to kick and ban city members and archive cities
+  Moderator,
+}
+
+impl Role {
+  // Role representation used as a storage key
+  pub fn key(&self) -> &'static str {
+    match self {
+      Role::CityCreator => "city_creator",
+      Role::Moderator => "moderator",
+    }
+  }
+}
+
+// Council is struct that is stored in cold storage by specific key, privileged actions need its approval if exists
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Council {
+  // Council members addresses
+  pub members: Vec<CanonicalAddr>,
+
+  // Amount of member approvals needed for executing a proposal
+  pub threshold: u64,
+}
+
+// CouncilResponse is struct for representing Council when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CouncilResponse {
+  // Council members addresses
+  pub members: Vec<Addr>,
+
+  // Amount of member approvals needed for executing a proposal
+  pub threshold: u64,
+}
+
+// CouncilProposal is struct that is stored in mapping by u64 key
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CouncilProposal {
+  // Council member created the proposal
+  pub proposer: CanonicalAddr,
+
+  // Privileged message executed on behalf of the contract once approved
+  pub action: ExecuteMsg,
+
+  // Proposal could not be approved or executed after expiration
+  pub expires_at: Expiration,
+
+  // Council members approved the proposal
+  pub approvals: Vec<CanonicalAddr>,
+}
+
+// CouncilProposalResponse is struct for representing CouncilProposal when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CouncilProposalResponse {
+  // Proposal id
+  pub id: u64,
+
+  // Proposal data
+  pub proposer: Addr,
+  pub action: ExecuteMsg,
+  pub expires_at: Expiration,
+
+  // Council members approved the proposal
+  pub approvals: Vec<Addr>,
+}
+
+// ElectionStatus is stage of maintainer election
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ElectionStatus {
+  // Candidates are nominated and voted for until voting period ends
+  Voting,
+  // Votes are counted, the winner is waiting for finalization
+  Tallied,
+  // Election is over, new nomination starts the next one
+  Finalized,
+}
+
+// Election is struct that is stored in cold storage by specific key, it keeps the latest election only
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Election {
+  // Election id
+  pub id: u64,
+
+  // Election stage
+  pub status: ElectionStatus,
+  // Candidates could not be nominated or voted for after voting period end
+  pub ends_at: Expiration,
+  // Quorum snapshot taken when election is started
+  pub quorum: u64,
+  // Number of nominated candidates
+  #[serde(default)]
+  pub candidates_count: u64,
+
+  // Total weight of votes
+  pub total_weight: u64,
+  // Candidate with the most votes, it is set by tally only if quorum is reached and there is no tie
+  pub winner: Option<CanonicalAddr>,
+}
+
+// ElectionResponse is struct for representing Election when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ElectionResponse {
+  // Election id
+  pub id: u64,
+
+  // Election data
+  pub status: ElectionStatus,
+  pub ends_at: Expiration,
+  pub quorum: u64,
+  pub total_weight: u64,
+  pub winner: Option<Addr>,
+}
+
+// Vote is struct that is stored in mapping by election id and voter address key
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Vote {
+  // Candidate voted for
+  pub candidate: CanonicalAddr,
+
+  // Voter cities count snapshot taken when voting
+  pub weight: u64,
+}
+
+// CandidateResponse is struct for representing candidate votes when querying
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CandidateResponse {
+  // Candidate address
+  pub candidate: Addr,
+
+  // Total weight of votes for the candidate
+  pub votes: u64,
+}
+
+// CitySeed is struct for providing city registered on instantiation
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct CitySeed {
+  // City metagata
+  pub name: CityName,
+  pub power_level: u8,
+}
+
 // City is struct that is stored in mapping by u64 key
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
 pub struct City {
@@ -39,6 +332,418 @@ pub struct City {
 
   // Counter of city members
   pub members_count: u64,
+
+  // City mayor is able to manage the city and appoint deputies next to maintainer
+  #[serde(default)]
+  pub mayor: Option<CanonicalAddr>,
+  // Counter of mayor deputies
+  #[serde(default)]
+  pub deputies_count: u64,
+  // Counter of city metadata changes
+  #[serde(default)]
+  pub changes_count: u64,
+
+  // City lifecycle stage
+  #[serde(default)]
+  pub status: CityStatus,
+  // Rule people join the city by
+  #[serde(default)]
+  pub join_policy: JoinPolicy,
+
+  // Maximal number of city members, people joining full city are put on waitlist
+  #[serde(default)]
+  pub max_members: Option<u64>,
+  // Number of people on waitlist
+  #[serde(default)]
+  pub waitlist_count: u64,
+  // Counter of waitlist tickets, tickets are issued in joining order
+  #[serde(default)]
+  pub waitlist_tickets_count: u64,
+
+  // Age limits of city members, members over maximal age are evicted by explicit sweep
+  #[serde(default)]
+  pub min_age: Option<u8>,
+  #[serde(default)]
+  pub max_age: Option<u8>,
+
+  // Fee in native denom people pay to join the city, it is credited to city treasury
+  #[serde(default)]
+  pub join_fee: Option<Coin>,
+
+  // Region the city is assigned to
+  #[serde(default)]
+  pub region_id: Option<u64>,
+
+  // Optional city metadata shown on frontend
+  #[serde(default)]
+  pub profile: CityProfile,
+
+  // City the members are moved to by merge, queries follow it once merge is finished
+  #[serde(default)]
+  pub merged_into: Option<u64>,
+  // Number of unfinished merges into the city, the city is not able to be archived or merged until they finish
+  #[serde(default)]
+  pub merges_in_progress: u64,
+}
+
+// CityProfile is optional city metadata, every field is validated on update
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default, JsonSchema)]
+pub struct CityProfile {
+  // Description may be up to 1024 bytes
+  pub description: Option<String>,
+  // ISO 3166-1 alpha-2 country code in upper case
+  pub country_code: Option<String>,
+  // Location of city center
+  pub coordinates: Option<Coordinates>,
+  // Website and image should be URIs up to 256 bytes, website uses http or https scheme
+  pub website: Option<String>,
+  pub image_uri: Option<String>,
+  // Date city was founded on
+  pub founded_on: Option<FoundingDate>,
+}
+
+// Coordinates are stored in microdegrees
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, JsonSchema)]
+pub struct Coordinates {
+  // Latitude may be in [-90000000:90000000]
+  pub latitude: i32,
+  // Longitude may be in [-180000000:180000000]
+  pub longitude: i32,
+}
+
+pub const MAX_LATITUDE: i32 = 90_000_000;
+pub const MAX_LONGITUDE: i32 = 180_000_000;
+pub const MICRODEGREES_IN_DEGREE: i64 = 1_000_000;
+
+// Geohash precision of city coordinates index in base32 characters, cell of such geohash is few meters wide
+pub const GEOHASH_PRECISION: usize = 9;
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+// Cosine of latitude in degrees [0:90] multiplied by 1000000, floats are not allowed in contracts
+const COS_BY_DEGREE: [i64; 91] = [
+  1000000, 999848, 999391, 998630, 997564, 996195, 994522, 992546, 990268, 987688, 984808, 981627, 978148, 974370,
+  970296, 965926, 961262, 956305, 951057, 945519, 939693, 933580, 927184, 920505, 913545, 906308, 898794, 891007,
+  882948, 874620, 866025, 857167, 848048, 838671, 829038, 819152, 809017, 798636, 788011, 777146, 766044, 754710,
+  743145, 731354, 719340, 707107, 694658, 681998, 669131, 656059, 642788, 629320, 615661, 601815, 587785, 573576,
+  559193, 544639, 529919, 515038, 500000, 484810, 469472, 453990, 438371, 422618, 406737, 390731, 374607, 358368,
+  342020, 325568, 309017, 292372, 275637, 258819, 241922, 224951, 207912, 190809, 173648, 156434, 139173, 121869,
+  104528, 87156, 69756, 52336, 34899, 17452, 0,
+];
+
+impl Coordinates {
+  // Geohash of the point with the precision, longitude and latitude cells are calculated with integer arithmetic
+  pub fn geohash(&self, precision: usize) -> String {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    geohash_cell(
+      cell_index(self.longitude, MAX_LONGITUDE, lon_bits),
+      cell_index(self.latitude, MAX_LATITUDE, lat_bits),
+      precision,
+    )
+  }
+
+  // Geohash cells of the precision containing the point and cells around it
+  pub fn neighbour_cells(&self, precision: usize) -> Vec<String> {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    let lon_index = cell_index(self.longitude, MAX_LONGITUDE, lon_bits) as i64;
+    let lat_index = cell_index(self.latitude, MAX_LATITUDE, lat_bits) as i64;
+
+    let mut cells = vec![];
+    for lat in (lat_index - 1)..=(lat_index + 1) {
+      // Cells are not wrapped over poles
+      if lat < 0 || lat >= 1 << lat_bits {
+        continue;
+      }
+      // Cells are wrapped over antimeridian
+      for lon in (lon_index - 1)..=(lon_index + 1) {
+        cells.push(geohash_cell(
+          lon.rem_euclid(1 << lon_bits) as u64,
+          lat as u64,
+          precision,
+        ));
+      }
+    }
+    cells.sort();
+    cells.dedup();
+
+    cells
+  }
+
+  // Distance every point closer than is within neighbour cells of the precision, in microdegrees of latitude
+  pub fn neighbourhood_radius(&self, precision: usize) -> i64 {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    let cell_height = (2 * MAX_LATITUDE as i64) >> lat_bits;
+    let cell_width = (2 * MAX_LONGITUDE as i64) >> lon_bits;
+
+    // Cell width is the shortest at the farthest latitude from equator
+    let farthest_latitude =
+      (self.latitude.unsigned_abs() as i64 + cell_height + MICRODEGREES_IN_DEGREE - 1) / MICRODEGREES_IN_DEGREE;
+    let cos = COS_BY_DEGREE[farthest_latitude.min(90) as usize];
+
+    cell_height.min(cell_width * cos / MICRODEGREES_IN_DEGREE)
+  }
+
+  // Squared distance to another point on equirectangular projection, in microdegrees of latitude
+  pub fn distance_squared(&self, other: &Coordinates) -> i64 {
+    let lat_distance = (self.latitude - other.latitude) as i64;
+    let lon_distance = (self.longitude as i64 - other.longitude as i64).abs();
+    let lon_distance = lon_distance.min(2 * MAX_LONGITUDE as i64 - lon_distance);
+
+    // Longitude distance is shortened by cosine of mean latitude
+    let mean_latitude = (self.latitude as i64 + other.latitude as i64).abs() / 2 / MICRODEGREES_IN_DEGREE;
+    let lon_distance = lon_distance * COS_BY_DEGREE[mean_latitude as usize] / MICRODEGREES_IN_DEGREE;
+
+    lat_distance * lat_distance + lon_distance * lon_distance
+  }
+
+  // Check the point is within area, the area is not wrapped over antimeridian
+  pub fn is_within(&self, min: &Coordinates, max: &Coordinates) -> bool {
+    (min.latitude..=max.latitude).contains(&self.latitude) && (min.longitude..=max.longitude).contains(&self.longitude)
+  }
+}
+
+// Geohash cells of the highest precision covering the area with at most max_cells cells
+// The whole world is covered by single empty cell if the area is too large
+pub fn geohash_area_cells(min: &Coordinates, max: &Coordinates, max_cells: u64) -> Vec<String> {
+  for precision in (1..=GEOHASH_PRECISION).rev() {
+    let (lon_bits, lat_bits) = geohash_bits(precision);
+    let lon_range =
+      cell_index(min.longitude, MAX_LONGITUDE, lon_bits)..=cell_index(max.longitude, MAX_LONGITUDE, lon_bits);
+    let lat_range = cell_index(min.latitude, MAX_LATITUDE, lat_bits)..=cell_index(max.latitude, MAX_LATITUDE, lat_bits);
+    if (lon_range.end() - lon_range.start() + 1) * (lat_range.end() - lat_range.start() + 1) > max_cells {
+      continue;
+    }
+
+    let mut cells = vec![];
+    for lon in lon_range {
+      for lat in lat_range.clone() {
+        cells.push(geohash_cell(lon, lat, precision));
+      }
+    }
+    cells.sort();
+
+    return cells;
+  }
+
+  vec![String::new()]
+}
+
+// Helper function for splitting geohash bits between longitude and latitude, longitude takes the extra bit
+fn geohash_bits(precision: usize) -> (u32, u32) {
+  let bits = 5 * precision as u32;
+  ((bits + 1) / 2, bits / 2)
+}
+
+// Helper function for getting index of cell containing value in [-max:max] split into 2^bits cells
+fn cell_index(value: i32, max: i32, bits: u32) -> u64 {
+  let index = (((value as i64 + max as i64) << bits) / (2 * max as i64)) as u64;
+  index.min((1 << bits) - 1)
+}
+
+// Helper function for encoding cell indexes to geohash by interleaving their bits, starting with longitude
+fn geohash_cell(lon_index: u64, lat_index: u64, precision: usize) -> String {
+  let (mut lon_bits, mut lat_bits) = geohash_bits(precision);
+  let mut geohash = String::with_capacity(precision);
+  let mut char_index = 0;
+  for bit in 0..5 * precision {
+    let value = if bit % 2 == 0 {
+      lon_bits -= 1;
+      (lon_index >> lon_bits) & 1
+    } else {
+      lat_bits -= 1;
+      (lat_index >> lat_bits) & 1
+    };
+    char_index = (char_index << 1) | value as usize;
+    if bit % 5 == 4 {
+      geohash.push(GEOHASH_ALPHABET[char_index] as char);
+      char_index = 0;
+    }
+  }
+
+  geohash
+}
+
+// FoundingDate is calendar date, month and day are optional as founding dates of old cities are often unknown
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct FoundingDate {
+  // Year may be negative for dates before common era, it may not be after current year
+  pub year: i32,
+  // Month may be in [1:12]
+  pub month: Option<u8>,
+  // Day may be set with month only, it may not exceed length of the month
+  pub day: Option<u8>,
+}
+
+// CityStatus is lifecycle stage of city
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CityStatus {
+  // People are able to register in the city
+  #[default]
+  Active,
+  // The city is readable but people are not able to register in it anymore
+  Archived,
+  // City members are unregistered in batches, the city is removed once it has no members
+  Deleting,
+  // City members are moved to another city in batches, the city is archived once it has no members
+  Merging,
+}
+
+// RegionKind is level of region in hierarchy, countries contain regions and regions contain cities
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionKind {
+  Country,
+  Region,
+}
+
+// Region is struct that is stored in mapping by region id key
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct Region {
+  // Region metadata
+  pub name: RegionName,
+  pub kind: RegionKind,
+  // Country containing the region, missed for countries
+  pub parent_id: Option<u64>,
+
+  // Counters of child regions and cities
+  pub children_count: u64,
+  pub cities_count: u64,
+  // Sum of populations of cities within the region
+  pub population: u64,
+
+  // Age limits inherited by cities within the region, they are checked on joining next to city limits
+  pub min_age: Option<u8>,
+  pub max_age: Option<u8>,
+}
+
+// RegionResponse is struct for representing Region when querying
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct RegionResponse {
+  // Region id
+  pub id: u64,
+
+  // Region metadata
+  pub name: RegionName,
+  pub kind: RegionKind,
+  pub parent_id: Option<u64>,
+
+  // Number of child regions and cities
+  pub regions_count: u64,
+  pub cities_count: u64,
+  // Sum of populations of cities within the region
+  pub population: u64,
+
+  // Age limits inherited by cities within the region
+  pub min_age: Option<u8>,
+  pub max_age: Option<u8>,
+}
+
+// CityChange is struct that is stored in mapping by city id and change id key
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct CityChange {
+  // Address changed the city and block time of the change
+  pub changed_by: CanonicalAddr,
+  pub changed_at: Timestamp,
+
+  // City metagata before the change
+  pub previous_name: CityName,
+  pub previous_power_level: u8,
+
+  // City metagata after the change
+  pub name: CityName,
+  pub power_level: u8,
+}
+
+// JoinPolicy is rule people join the city by, invited people join the city regardless of the policy
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinPolicy {
+  // Anyone is able to join the city
+  #[default]
+  Open,
+  // Joining creates join request approved or rejected by city admin
+  ApprovalRequired,
+  // Only invited people are able to join the city
+  InviteOnly,
+}
+
+// JoinRequest is struct that is stored in mapping by city id and person address until approved or rejected
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct JoinRequest {
+  // Request is not able to be approved after expiration
+  pub expires_at: Expiration,
+}
+
+// CityBan is struct that is stored in mapping by city id and person address
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct CityBan {
+  // Banned person is not able to join the city until expiration
+  pub until: Expiration,
+}
+
+// CityBanResponse is struct for representing CityBan when querying
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct CityBanResponse {
+  pub person: Addr,
+  pub until: Expiration,
+}
+
+// JoinRequestResponse is struct for representing JoinRequest when querying
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct JoinRequestResponse {
+  pub city_id: u64,
+  pub person: Addr,
+  pub expires_at: Expiration,
+}
+
+// InviteCode is struct that is stored in mapping by city id and code hash, only hash of secret code is stored
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct InviteCode {
+  // Code is not able to be used after expiration
+  pub expires_at: Expiration,
+  // Number of registrations left, the code is removed once it is used up
+  pub remaining_uses: u64,
+}
+
+// InviteCodeResponse is struct for representing InviteCode when querying
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct InviteCodeResponse {
+  pub city_id: u64,
+  pub code_hash: String,
+  pub expires_at: Expiration,
+  pub remaining_uses: u64,
+}
+
+// CityOffice is position held by city administrator
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CityOffice {
+  // Is appointed by maintainer, is able to manage the city and appoint deputies
+  Mayor,
+  // Is appointed by mayor, is able to manage the city
+  Deputy,
+}
+
+// AdministeredCityResponse is struct for representing city administered by address when querying
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct AdministeredCityResponse {
+  pub city_id: u64,
+  pub office: CityOffice,
+}
+
+// CityChangeResponse is struct for representing CityChange when querying
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, JsonSchema)]
+pub struct CityChangeResponse {
+  // Change id
+  pub id: u64,
+
+  // Change data
+  pub changed_by: Addr,
+  pub changed_at: Timestamp,
+  pub previous_name: CityName,
+  pub previous_power_level: u8,
+  pub name: CityName,
+  pub power_level: u8,
 }
 
 // CityResponse is struct for representing City when querying
@@ -53,6 +758,26 @@ pub struct CityResponse {
 
   // Number of city members
   pub population: u64,
+
+  // City lifecycle stage
+  pub status: CityStatus,
+  // Rule people join the city by
+  pub join_policy: JoinPolicy,
+  // Maximal number of city members
+  pub max_members: Option<u64>,
+  // Number of people on waitlist
+  pub waitlist_length: u64,
+  // Age limits of city members
+  pub min_age: Option<u8>,
+  pub max_age: Option<u8>,
+  // Fee people pay to join the city
+  pub join_fee: Option<Coin>,
+  // Region the city is assigned to
+  pub region_id: Option<u64>,
+  // Optional city metadata
+  pub profile: CityProfile,
+  // City the city is merged into
+  pub merged_into: Option<u64>,
 }
 
 // Person is struct that is stored in mapping by account address key
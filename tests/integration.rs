@@ -22,7 +22,7 @@ use cosmwasm_vm::testing::{execute, instantiate, mock_info, mock_instance, query
 use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
 use cosmwasm_template::{
   msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ResponseMsg},
-  utils::{Birthday, CityResponse, PersonResponse},
+  utils::{Birthday, CityProfile, CityResponse, CityStatus, JoinPolicy, PersonResponse},
 };
 
 // This line will test the output of `make code.build`
@@ -30,6 +30,23 @@ static WASM: &[u8] = include_bytes!("../.cargo_target/wasm32-unknown-unknown/rel
 // This line will test the output of `make code.build.optimize`
 // static WASM: &[u8] = include_bytes!("../artifacts/cosmwasm_template.wasm");
 
+// Generate default `InstantiateMsg`
+fn mock_instantiate_msg() -> InstantiateMsg {
+  InstantiateMsg {
+    maintainer: None,
+    maintainer_requirement_name: Some("Super_Maintainer_887".to_string()),
+    maintainer_requirement_age: Some(17),
+    maintainer_claim_window: Some(86400),
+    election_voting_period: Some(3600),
+    election_quorum: Some(2),
+    max_page_size: None,
+    city_deletion_batch_size: None,
+    join_request_period: None,
+    protocol_fee_share: None,
+    seed_cities: None,
+  }
+}
+
 // Generate `env`, `info`
 fn mock_env_info_height(signer: &str, height: u64, time: u64) -> (Env, MessageInfo) {
   let env = Env {
@@ -54,7 +71,7 @@ fn initialization() {
   let (env, info) = mock_env_info_height("creator", 887, 31556952 * 52 + 31556952 / 2); // in middle of 2022
 
   // Instantiate contract
-  let res: Response = instantiate(&mut deps, env.clone(), info, InstantiateMsg {}).unwrap();
+  let res: Response = instantiate(&mut deps, env.clone(), info, mock_instantiate_msg()).unwrap();
   assert_eq!(0, res.messages.len());
 
   // Get maintainer
@@ -65,7 +82,8 @@ fn initialization() {
   assert_eq!(
     maintainer,
     ResponseMsg::LookMaintainer {
-      maintainer: Addr::unchecked("creator")
+      maintainer: Some(Addr::unchecked("creator")),
+      frozen: false,
     }
   );
 }
@@ -77,7 +95,7 @@ fn person_in_city_registration() {
   let (env_2, info_2) = mock_env_info_height("user_2", 887, 31556952 * 52 + 31556952 / 2);
   let (env_3, info_3) = mock_env_info_height("user_3", 887, 31556952 * 52 + 31556952 / 2);
 
-  let _: Response = instantiate(&mut deps, env_1.clone(), info_1.clone(), InstantiateMsg {}).unwrap();
+  let _: Response = instantiate(&mut deps, env_1.clone(), info_1.clone(), mock_instantiate_msg()).unwrap();
 
   let _: Response = execute(
     &mut deps,
@@ -127,7 +145,10 @@ fn person_in_city_registration() {
     &mut deps,
     env_2.clone(),
     info_2.clone(),
-    ExecuteMsg::RegisterInCity { city_id: 0 },
+    ExecuteMsg::RegisterInCity {
+      city_id: 0,
+      invite_code: None,
+    },
   )
   .unwrap();
 
@@ -135,14 +156,20 @@ fn person_in_city_registration() {
     &mut deps,
     env_3.clone(),
     info_3.clone(),
-    ExecuteMsg::RegisterInCity { city_id: 0 },
+    ExecuteMsg::RegisterInCity {
+      city_id: 0,
+      invite_code: None,
+    },
   )
   .unwrap();
   let _: Response = execute(
     &mut deps,
     env_3.clone(),
     info_3.clone(),
-    ExecuteMsg::RegisterInCity { city_id: 1 },
+    ExecuteMsg::RegisterInCity {
+      city_id: 1,
+      invite_code: None,
+    },
   )
   .unwrap();
 
@@ -229,7 +256,17 @@ fn person_in_city_registration() {
         id: 0,
         name: "Super City".to_string(),
         power_level: 3,
-        population: 2
+        population: 2,
+        status: CityStatus::Active,
+        join_policy: JoinPolicy::Open,
+        max_members: None,
+        waitlist_length: 0,
+        min_age: None,
+        max_age: None,
+        join_fee: None,
+        region_id: None,
+        profile: CityProfile::default(),
+        merged_into: None
       }]
       .to_vec()
     }
@@ -256,13 +293,33 @@ fn person_in_city_registration() {
           id: 0,
           name: "Super City".to_string(),
           power_level: 3,
-          population: 2
+          population: 2,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         },
         CityResponse {
           id: 1,
           name: "Secret City".to_string(),
           power_level: 3,
-          population: 1
+          population: 1,
+          status: CityStatus::Active,
+          join_policy: JoinPolicy::Open,
+          max_members: None,
+          waitlist_length: 0,
+          min_age: None,
+          max_age: None,
+          join_fee: None,
+          region_id: None,
+          profile: CityProfile::default(),
+          merged_into: None
         },
       ]
       .to_vec()